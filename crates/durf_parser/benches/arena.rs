@@ -0,0 +1,106 @@
+//! Compare the tree of shared nodes and its arena snapshot on the test
+//! pages.
+//!
+//! Run with `cargo bench -p durf_parser --bench arena`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use durf_parser::{ArenaAst, ArenaData, Ast, ParseFlags, RawNode};
+
+/// The pages parsed by the tests.
+const FIXTURES: [(&str, &str); 4] = [
+    ("page_1", include_str!("../fixtures/page_1.html")),
+    ("page_2", include_str!("../fixtures/page_2.html")),
+    ("page_3_jp", include_str!("../fixtures/page_3_jp.html")),
+    ("page_4_jp", include_str!("../fixtures/page_4_jp.html")),
+];
+
+/// Time a function, returning the mean duration of an iteration.
+fn time(name: &str, iterations: u32, mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let mean = start.elapsed() / iterations;
+    println!("  {name:<24} {mean:>12.2?}");
+    mean
+}
+
+fn tree_text_bytes(node: &RawNode) -> usize {
+    let own = match node {
+        RawNode::Text(text) => text.fragments.iter().map(|f| f.text.len()).sum(),
+        _ => 0,
+    };
+    own + node
+        .children()
+        .iter()
+        .map(|n| tree_text_bytes(n))
+        .sum::<usize>()
+}
+
+fn arena_text_bytes(arena: &ArenaAst) -> usize {
+    arena
+        .descendants(arena.root())
+        .map(|id| match &arena.node(id).data {
+            ArenaData::Text(text) => arena.fragments(text).map(|(t, _)| t.len()).sum(),
+            _ => 0,
+        })
+        .sum()
+}
+
+fn bench(name: &str, document: &str, iterations: u32) {
+    let flags = || ParseFlags {
+        limits: durf_parser::ParseLimits {
+            max_parse_time: None,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut ast = Ast::from_html(document, flags()).unwrap();
+    ast.minimize();
+    let arena = ast.to_arena().unwrap();
+    println!(
+        "{name}: {} bytes, {} nodes",
+        document.len(),
+        ast.root.node_count()
+    );
+
+    time("parse", iterations, || {
+        black_box(Ast::from_html(document, flags()).unwrap());
+    });
+    time("tree to arena", iterations, || {
+        black_box(ast.to_arena().unwrap());
+    });
+    time("arena to tree", iterations, || {
+        black_box(arena.to_ast());
+    });
+    let tree_walk = time("walk tree", iterations, || {
+        black_box(tree_text_bytes(&ast.root));
+    });
+    let arena_walk = time("walk arena", iterations, || {
+        black_box(arena_text_bytes(&arena));
+    });
+    assert_eq!(tree_text_bytes(&ast.root), arena_text_bytes(&arena));
+    println!(
+        "  walk speedup {:.1}x",
+        tree_walk.as_secs_f64() / arena_walk.as_secs_f64(),
+    );
+}
+
+fn main() {
+    for (name, page) in FIXTURES {
+        bench(name, page, 1000);
+    }
+
+    // A multi-megabyte document of the pages repeated in sections.
+    let large: String = (0..100)
+        .flat_map(|i| {
+            FIXTURES
+                .iter()
+                .map(move |(name, page)| format!("<section id=\"{name}-{i}\">{page}</section>"))
+        })
+        .collect();
+    let large = format!("<html><body>{large}</body></html>");
+    bench("large", &large, 5);
+}
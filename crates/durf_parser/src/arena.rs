@@ -0,0 +1,355 @@
+//! A compact, read-only snapshot of an AST, stored in an arena.
+//!
+//! [`Ast`] itself stays a tree of shared nodes, which the parser, minimize,
+//! diff, and search work on. An [`ArenaAst`] is an export of it for
+//! consumers that walk or keep a large document without editing it: nodes
+//! live in one vector and are addressed by [`NodeId`], with links to their
+//! parents, fragment text shares a single string buffer, and equal
+//! attributes are stored once. Edits are made to the tree and exported
+//! again.
+
+use super::*;
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+/// Id of a node in an [`ArenaAst`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    /// Index of the node in breadth-first order.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Fragments of a text, stored in an [`ArenaAst`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FragmentRange(Range<u32>);
+
+impl FragmentRange {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A text fragment, with its text in the shared buffer and its attributes
+/// in the attribute table.
+#[derive(Copy, Clone, Debug)]
+struct ArenaFragment {
+    text: (u32, u32),
+    attributes: u32,
+}
+
+/// A node in an [`ArenaAst`].
+#[derive(Clone, Debug)]
+pub struct ArenaNode {
+    parent: Option<NodeId>,
+    children: Range<u32>,
+    pub data: ArenaData,
+}
+
+impl ArenaNode {
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// Ids of the children of the node, in order.
+    pub fn children(&self) -> impl DoubleEndedIterator<Item = NodeId> + ExactSizeIterator + use<> {
+        self.children.clone().map(NodeId)
+    }
+}
+
+/// Content of a node in an [`ArenaAst`]. Child nodes are stored in the arena.
+#[derive(Clone, Debug)]
+pub enum ArenaData {
+    Empty,
+    Section {
+        title: Option<FragmentRange>,
+        ordering: SectionOrdering,
+        kind: SectionKind,
+        lang: Option<String>,
+        dir: Option<Direction>,
+    },
+    Text(FragmentRange),
+    Details {
+        summary: FragmentRange,
+        open: bool,
+    },
+    /// A form, without its nodes.
+    Form(Form),
+    Field(Field),
+    Code {
+        language: Option<String>,
+        text: FragmentRange,
+    },
+}
+
+/// A read-only snapshot of an AST with nodes stored in an arena.
+#[derive(Clone, Debug)]
+pub struct ArenaAst {
+    nodes: Vec<ArenaNode>,
+    fragments: Vec<ArenaFragment>,
+    attributes: Vec<TextAttributes>,
+    strings: String,
+    /// Language of the document.
+    pub lang: Option<Arc<str>>,
+    /// Text direction of the document.
+    pub dir: Option<Direction>,
+    /// Diagnostics from parsing.
+    pub report: Arc<ParseReport>,
+}
+
+impl ArenaAst {
+    /// Export an AST into an arena.
+    /// Fails with a resource limit if the nodes, fragments, or text do not
+    /// fit in 32-bit indices.
+    pub fn from_ast(ast: &Ast) -> Result<Self, Error> {
+        let mut arena = Self {
+            nodes: Vec::new(),
+            fragments: Vec::new(),
+            attributes: Vec::new(),
+            strings: String::new(),
+            lang: ast.lang.clone(),
+            dir: ast.dir,
+            report: ast.report.clone(),
+        };
+        let mut interned: HashMap<TextAttributes, u32> = HashMap::new();
+
+        // Nodes are stored breadth-first, so children are contiguous.
+        let data = arena.data(&ast.root, &mut interned)?;
+        arena.nodes.push(ArenaNode {
+            parent: None,
+            children: 0..0,
+            data,
+        });
+        let mut queue: VecDeque<(u32, &RawNode)> = VecDeque::from([(0, &*ast.root)]);
+        while let Some((id, raw)) = queue.pop_front() {
+            let start = index(arena.nodes.len(), Limit::Nodes)?;
+            for child in raw.children() {
+                let data = arena.data(child, &mut interned)?;
+                queue.push_back((index(arena.nodes.len(), Limit::Nodes)?, child));
+                arena.nodes.push(ArenaNode {
+                    parent: Some(NodeId(id)),
+                    children: 0..0,
+                    data,
+                });
+            }
+            arena.nodes[id as usize].children = start..index(arena.nodes.len(), Limit::Nodes)?;
+        }
+        Ok(arena)
+    }
+
+    /// Copy the nodes back into a tree.
+    pub fn to_ast(&self) -> Ast {
+        Ast {
+            root: Node::new(self.raw_node(self.root())),
+            lang: self.lang.clone(),
+            dir: self.dir,
+            report: self.report.clone(),
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node(&self, id: NodeId) -> &ArenaNode {
+        &self.nodes[id.index()]
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.node(id).parent
+    }
+
+    pub fn children(
+        &self,
+        id: NodeId,
+    ) -> impl DoubleEndedIterator<Item = NodeId> + ExactSizeIterator + use<> {
+        self.node(id).children()
+    }
+
+    /// Ids of the ancestors of a node, from its parent to the root.
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), |id| self.parent(*id))
+    }
+
+    /// Ids of a node and its descendents, depth-first.
+    pub fn descendants(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let mut stack = vec![id];
+        std::iter::from_fn(move || {
+            let id = stack.pop()?;
+            stack.extend(self.children(id).rev());
+            Some(id)
+        })
+    }
+
+    /// Text and attributes of fragments.
+    pub fn fragments(
+        &self,
+        range: &FragmentRange,
+    ) -> impl Iterator<Item = (&str, &TextAttributes)> + '_ {
+        self.fragments[range.0.start as usize..range.0.end as usize]
+            .iter()
+            .map(|f| {
+                let (start, end) = f.text;
+                (
+                    &self.strings[start as usize..end as usize],
+                    &self.attributes[f.attributes as usize],
+                )
+            })
+    }
+
+    /// Collect the text of fragments.
+    pub fn collect(&self, range: &FragmentRange) -> String {
+        self.fragments(range).map(|(text, _)| text).collect()
+    }
+
+    /// Store the content of a node, without its children.
+    fn data(
+        &mut self,
+        raw: &RawNode,
+        interned: &mut HashMap<TextAttributes, u32>,
+    ) -> Result<ArenaData, Error> {
+        Ok(match raw {
+            RawNode::Empty => ArenaData::Empty,
+            RawNode::Section(section) => ArenaData::Section {
+                title: match &section.title {
+                    Some(title) => Some(self.store(title, interned)?),
+                    None => None,
+                },
+                ordering: section.ordering,
+                kind: section.kind,
+                lang: section.lang.clone(),
+                dir: section.dir,
+            },
+            RawNode::Text(text) => ArenaData::Text(self.store(text, interned)?),
+            RawNode::Details(details) => ArenaData::Details {
+                summary: self.store(&details.summary, interned)?,
+                open: details.open,
+            },
+            RawNode::Form(form) => ArenaData::Form(Form {
+                action: form.action.clone(),
+                method: form.method,
+                enctype: form.enctype.clone(),
+                nodes: Vec::new(),
+            }),
+            RawNode::Field(field) => ArenaData::Field(field.clone()),
+            RawNode::Code(code) => ArenaData::Code {
+                language: code.language.clone(),
+                text: self.store(&code.text, interned)?,
+            },
+        })
+    }
+
+    /// Store the fragments of a text.
+    fn store(
+        &mut self,
+        text: &Text,
+        interned: &mut HashMap<TextAttributes, u32>,
+    ) -> Result<FragmentRange, Error> {
+        let start = index(self.fragments.len(), Limit::Nodes)?;
+        for frag in &text.fragments {
+            let attributes = match interned.get(&frag.attributes) {
+                Some(attributes) => *attributes,
+                None => {
+                    let attributes = index(self.attributes.len(), Limit::Nodes)?;
+                    self.attributes.push(frag.attributes.clone());
+                    interned.insert(frag.attributes.clone(), attributes);
+                    attributes
+                }
+            };
+            let text_start = index(self.strings.len(), Limit::TextBytes)?;
+            self.strings.push_str(&frag.text);
+            self.fragments.push(ArenaFragment {
+                text: (text_start, index(self.strings.len(), Limit::TextBytes)?),
+                attributes,
+            });
+        }
+        Ok(FragmentRange(
+            start..index(self.fragments.len(), Limit::Nodes)?,
+        ))
+    }
+
+    /// Copy fragments into a text.
+    fn text(&self, range: &FragmentRange) -> Text {
+        Text {
+            fragments: self
+                .fragments(range)
+                .map(|(text, attributes)| TextFragment::new(text, Some(attributes.clone())))
+                .collect(),
+        }
+    }
+
+    /// Copy a node and its descendents into a tree.
+    fn raw_node(&self, id: NodeId) -> RawNode {
+        let nodes = || -> Vec<Node> {
+            self.children(id)
+                .map(|child| Node::new(self.raw_node(child)))
+                .collect()
+        };
+        match &self.node(id).data {
+            ArenaData::Empty => RawNode::Empty,
+            ArenaData::Section {
+                title,
+                ordering,
+                kind,
+                lang,
+                dir,
+            } => RawNode::Section(Section {
+                title: title.as_ref().map(|t| self.text(t)),
+                nodes: nodes(),
+                ordering: *ordering,
+                kind: *kind,
+                lang: lang.clone(),
+                dir: *dir,
+            }),
+            ArenaData::Text(text) => RawNode::Text(self.text(text)),
+            ArenaData::Details { summary, open } => RawNode::Details(Details {
+                summary: self.text(summary),
+                nodes: nodes(),
+                open: *open,
+            }),
+            ArenaData::Form(form) => RawNode::Form(Form {
+                nodes: nodes(),
+                ..form.clone()
+            }),
+            ArenaData::Field(field) => RawNode::Field(field.clone()),
+            ArenaData::Code { language, text } => RawNode::Code(Code {
+                language: language.clone(),
+                text: self.text(text),
+            }),
+        }
+    }
+}
+
+/// Convert a length to an arena index, failing if it does not fit.
+fn index(len: usize, limit: Limit) -> Result<u32, Error> {
+    u32::try_from(len).map_err(|_| Error::ResourceLimit {
+        limit,
+        max: u32::MAX as usize,
+        path: String::new(),
+    })
+}
+
+impl Ast {
+    /// Export the AST into a read-only arena, for cheap traversal with
+    /// parent links.
+    pub fn to_arena(&self) -> Result<ArenaAst, Error> {
+        ArenaAst::from_ast(self)
+    }
+}
@@ -1,27 +1,88 @@
 use super::*;
 
+// Documents may be parsed on one thread and rendered or shared on others.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Ast>();
+    assert_send_sync::<Node>();
+    assert_send_sync::<ArenaAst>();
+};
+
 /// A parsed AST representing a document.
+/// Clones share the nodes of the document, which are copied on write, and
+/// its language and report.
 #[allow(unused)]
 #[derive(Clone, Debug)]
 pub struct Ast {
     pub root: Node,
+    /// Language of the document, from the `lang` of its root element.
+    pub lang: Option<Arc<str>>,
+    /// Text direction of the document, from the `dir` of its root element.
+    pub dir: Option<Direction>,
+    /// Diagnostics from parsing.
+    pub report: Arc<ParseReport>,
 }
 
 impl Ast {
     /// Pares the AST from HTML.
     pub fn from_html(document: &str, flags: ParseFlags) -> Result<Ast, Error> {
-        let mut flags = flags;
+        flags.validate()?;
+        Self::parse_html(document, ParseContext::new(flags))
+    }
+
+    /// Parse HTML with a started parse.
+    fn parse_html(document: &str, mut flags: ParseContext) -> Result<Ast, Error> {
+        let input_bytes = document.len();
+        let document = truncate_str(document, flags.limits.max_input_bytes);
+        if document.len() < input_bytes {
+            flags.hit_limit(Limit::InputBytes);
+        }
         let parsed_doc = scraper::Html::parse_document(document);
         let parsed_root = parsed_doc.root_element();
-        let new_root = RawNode::from_element_ref(&parsed_root, &mut flags)?;
+        let mut new_root = RawNode::from_element_ref(&parsed_root, &mut flags)
+            .map_err(|e| e.context("failed to parse document root"))?;
+        new_root.clean(&flags.clean);
+        new_root.sanitize(&flags.sanitize);
+        let (lang, dir) = Direction::from_element(&parsed_root, &mut flags);
 
+        let mut report = std::mem::take(&mut flags.report);
+        report.input_bytes = input_bytes;
+        report.nodes = new_root.node_count();
         Ok(Ast {
             root: Node::new(new_root),
+            lang: lang.map(Arc::from),
+            dir,
+            report: Arc::new(report),
         })
     }
 
+    /// Parse the AST from HTML bytes in an unknown encoding.
+    /// The encoding is detected from a byte order mark, the `charset` of the
+    /// content type, or a `<meta>` declaration, in that order, and recorded
+    /// in the report.
+    pub fn from_html_bytes(
+        document: &[u8],
+        content_type: Option<&str>,
+        flags: ParseFlags,
+    ) -> Result<Ast, Error> {
+        flags.validate()?;
+        let mut flags = ParseContext::new(flags);
+        let input_bytes = document.len();
+        let document = &document[..input_bytes.min(flags.limits.max_input_bytes)];
+        if document.len() < input_bytes {
+            flags.hit_limit(Limit::InputBytes);
+        }
+        let (document, encoding) = crate::encoding::decode(document, content_type)?;
+
+        let mut ast = Self::parse_html(&document, flags)?;
+        let report = Arc::make_mut(&mut ast.report);
+        report.input_bytes = input_bytes;
+        report.encoding = Some(encoding);
+        Ok(ast)
+    }
+
     /// Experimental: parse the AST from text.
-    pub fn from_text(document: &str, _flags: ParseFlags) -> Result<Ast, Error> {
+    pub fn from_text(document: &str, flags: ParseFlags) -> Result<Ast, Error> {
         let mut section = Section::new_set();
 
         for p in document.split("\n") {
@@ -30,20 +91,68 @@ impl Ast {
                 .push(Node::new(RawNode::Text(Text::from_fragment(p))));
         }
 
+        let mut root = RawNode::Section(section);
+        root.clean(&flags.clean);
+        root.sanitize(&flags.sanitize);
+        let report = ParseReport {
+            input_bytes: document.len(),
+            nodes: root.node_count(),
+            ..Default::default()
+        };
         Ok(Ast {
-            root: Node::new(RawNode::Section(section)),
+            root: Node::new(root),
+            lang: None,
+            dir: None,
+            report: Arc::new(report),
         })
     }
 
+    /// Parse the AST from a document with a MIME content type, like
+    /// `text/html; charset=utf-8`.
+    pub fn from_content_type(
+        document: &str,
+        content_type: &str,
+        flags: ParseFlags,
+    ) -> Result<Ast, Error> {
+        let mime = content_type.split(';').next().unwrap_or_default().trim();
+        match mime.to_ascii_lowercase().as_ref() {
+            "text/html" | "application/xhtml+xml" => Self::from_html(document, flags),
+            "text/plain" => Self::from_text(document, flags),
+            _ => Err(Error::UnsupportedFormat {
+                format: mime.into(),
+            }),
+        }
+    }
+
     /// Minimize the AST.
     pub fn minimize(&mut self) {
         self.root.minimize();
     }
+
+    /// Minimize the AST with options.
+    pub fn minimize_with(&mut self, options: &MinimizeOptions) {
+        self.root.minimize_with(options);
+    }
+
+    /// Find the main content of the document.
+    /// This is the first `main` section, falling back to the first article.
+    pub fn main_content(&self) -> Option<&Section> {
+        self.root
+            .find_section(SectionKind::Main)
+            .or_else(|| self.root.find_section(SectionKind::Article))
+    }
+
+    /// Restructure the AST so each heading titles a section containing the
+    /// nodes up to the next heading of equal or higher level.
+    /// This works best on a minimized AST.
+    pub fn sectionize(&mut self) {
+        self.root.sectionize();
+    }
 }
 
 impl std::fmt::Display for Ast {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "AST:\n")?;
+        writeln!(f, "AST:")?;
         self.root.export_string(f, 0)
     }
 }
@@ -0,0 +1,296 @@
+//! Building ASTs in code, for fixtures and generated pages.
+
+use super::*;
+
+/// Builder of an AST, or of the nodes of a section within one.
+#[derive(Clone, Debug)]
+pub struct AstBuilder {
+    section: Section,
+}
+
+impl Default for AstBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AstBuilder {
+    pub fn new() -> Self {
+        Self::from_section(Section::new_set())
+    }
+
+    fn from_section(section: Section) -> Self {
+        Self { section }
+    }
+
+    /// Set the language tag of the document or section.
+    pub fn lang(mut self, lang: impl Into<String>) -> Self {
+        self.section.lang = Some(lang.into());
+        self
+    }
+
+    /// Set the text direction of the document or section.
+    pub fn dir(mut self, dir: Direction) -> Self {
+        self.section.dir = Some(dir);
+        self
+    }
+
+    /// Set the semantic kind of the section.
+    pub fn kind(mut self, kind: SectionKind) -> Self {
+        self.section.kind = kind;
+        self
+    }
+
+    /// Add a node.
+    pub fn node(mut self, node: impl Into<RawNode>) -> Self {
+        self.section.nodes.push(Node::new(node.into()));
+        self
+    }
+
+    /// Add a heading, 1-6.
+    pub fn heading(self, level: u8, text: impl Into<String>) -> Self {
+        self.node(TextBuilder::new().heading(level, text).build())
+    }
+
+    /// Add a paragraph of plain text.
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.node(TextBuilder::new().text(text).build())
+    }
+
+    /// Add a paragraph of styled fragments.
+    pub fn para(self, build: impl FnOnce(TextBuilder) -> TextBuilder) -> Self {
+        self.node(build(TextBuilder::new()).build())
+    }
+
+    /// Add a block of code.
+    pub fn code(self, language: Option<&str>, code: impl Into<String>) -> Self {
+        self.node(Code::new(code, language.map(String::from)))
+    }
+
+    /// Add a bulleted list, with each node as an item.
+    pub fn list(self, build: impl FnOnce(AstBuilder) -> AstBuilder) -> Self {
+        self.node(build(Self::from_section(Section::new_list())).section)
+    }
+
+    /// Add an enumerated list, with each node as an item.
+    pub fn enumeration(self, build: impl FnOnce(AstBuilder) -> AstBuilder) -> Self {
+        self.node(build(Self::from_section(Section::new_enumeration())).section)
+    }
+
+    /// Add a titled section.
+    pub fn section(
+        self,
+        title: impl Into<String>,
+        build: impl FnOnce(AstBuilder) -> AstBuilder,
+    ) -> Self {
+        let section = Section::new_set().with_title(TextBuilder::new().text(title).build());
+        self.node(build(Self::from_section(section)).section)
+    }
+
+    /// Add a closed disclosure.
+    pub fn details(
+        self,
+        summary: impl Into<String>,
+        build: impl FnOnce(AstBuilder) -> AstBuilder,
+    ) -> Self {
+        let mut details = Details::new(TextBuilder::new().text(summary).build());
+        details.nodes = build(Self::new()).section.nodes;
+        self.node(details)
+    }
+
+    /// Build the section, sanitized like parsed text.
+    pub fn build_section(self) -> Section {
+        let mut node = RawNode::from(self.section);
+        node.sanitize(&SanitizeFlags::default());
+        match node {
+            RawNode::Section(section) => section,
+            _ => unreachable!("sanitizing keeps the section"),
+        }
+    }
+
+    /// Build an AST, with the language and direction of the root as those
+    /// of the document.
+    /// Text is sanitized with the default flags, like parsed text.
+    pub fn build(self) -> Ast {
+        let lang = self.section.lang.as_deref().map(Arc::from);
+        let dir = self.section.dir;
+        Ast {
+            lang,
+            dir,
+            root: Node::new(self.build_section().into()),
+            report: Default::default(),
+        }
+    }
+}
+
+/// Builder of a text from styled fragments.
+#[derive(Clone, Debug, Default)]
+pub struct TextBuilder {
+    text: Text,
+}
+
+impl TextBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a fragment with attributes.
+    pub fn fragment(mut self, text: impl Into<String>, attributes: TextAttributes) -> Self {
+        self.text.append(TextFragment::new(text, Some(attributes)));
+        self
+    }
+
+    /// Add plain text.
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.fragment(text, TextAttributes::new())
+    }
+
+    pub fn bold(self, text: impl Into<String>) -> Self {
+        self.fragment(
+            text,
+            TextAttributes {
+                bold: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    pub fn italic(self, text: impl Into<String>) -> Self {
+        self.fragment(
+            text,
+            TextAttributes {
+                italic: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Add inline code.
+    pub fn code(self, text: impl Into<String>) -> Self {
+        self.fragment(
+            text,
+            TextAttributes {
+                preformatted: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    pub fn link(self, text: impl Into<String>, link: impl Into<String>) -> Self {
+        self.fragment(
+            text,
+            TextAttributes {
+                link: Some(link.into()),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Add heading text, 1-6.
+    pub fn heading(self, level: u8, text: impl Into<String>) -> Self {
+        self.fragment(
+            text,
+            TextAttributes {
+                heading: Some(level.clamp(1, 6)),
+                ..Default::default()
+            },
+        )
+    }
+
+    pub fn build(self) -> Text {
+        self.text
+    }
+}
+
+impl From<TextBuilder> for Text {
+    fn from(value: TextBuilder) -> Self {
+        value.build()
+    }
+}
+
+/// Build an [`Ast`] with [`AstBuilder`].
+///
+/// Nodes end with `;`. Text is a literal or a parenthesized expression.
+///
+/// ```
+/// # use durf_parser::durf;
+/// let url = "https://example.com";
+/// let ast = durf! {
+///     h1 "Bookmarks";
+///     p { "Saved " b "pages" " from " a(url) "durf" };
+///     ul { "One"; p { i "Two" }; };
+///     ol { "First"; };
+///     section "More" { pre("rust") "fn main() {}"; };
+///     details "Hidden" { "Shown when open"; };
+/// };
+/// assert!(ast.to_string().contains("# Bookmarks"));
+/// ```
+#[macro_export]
+macro_rules! durf {
+    // Nodes of a section.
+    (@nodes $b:expr;) => { $b };
+    (@nodes $b:expr; h1 $t:tt; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.heading(1, $t); $($rest)*)
+    };
+    (@nodes $b:expr; h2 $t:tt; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.heading(2, $t); $($rest)*)
+    };
+    (@nodes $b:expr; h3 $t:tt; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.heading(3, $t); $($rest)*)
+    };
+    (@nodes $b:expr; h4 $t:tt; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.heading(4, $t); $($rest)*)
+    };
+    (@nodes $b:expr; h5 $t:tt; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.heading(5, $t); $($rest)*)
+    };
+    (@nodes $b:expr; h6 $t:tt; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.heading(6, $t); $($rest)*)
+    };
+    (@nodes $b:expr; p { $($text:tt)* }; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.para(|p| $crate::durf!(@text p; $($text)*)); $($rest)*)
+    };
+    (@nodes $b:expr; pre($lang:expr) $t:tt; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.code(Some($lang), $t); $($rest)*)
+    };
+    (@nodes $b:expr; pre $t:tt; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.code(None, $t); $($rest)*)
+    };
+    (@nodes $b:expr; ul { $($nodes:tt)* }; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.list(|l| $crate::durf!(@nodes l; $($nodes)*)); $($rest)*)
+    };
+    (@nodes $b:expr; ol { $($nodes:tt)* }; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.enumeration(|l| $crate::durf!(@nodes l; $($nodes)*)); $($rest)*)
+    };
+    (@nodes $b:expr; section $t:tt { $($nodes:tt)* }; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.section($t, |s| $crate::durf!(@nodes s; $($nodes)*)); $($rest)*)
+    };
+    (@nodes $b:expr; details $t:tt { $($nodes:tt)* }; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.details($t, |d| $crate::durf!(@nodes d; $($nodes)*)); $($rest)*)
+    };
+    (@nodes $b:expr; $t:tt; $($rest:tt)*) => {
+        $crate::durf!(@nodes $b.text($t); $($rest)*)
+    };
+
+    // Fragments of a text.
+    (@text $t:expr;) => { $t };
+    (@text $t:expr; b $f:tt $($rest:tt)*) => {
+        $crate::durf!(@text $t.bold($f); $($rest)*)
+    };
+    (@text $t:expr; i $f:tt $($rest:tt)*) => {
+        $crate::durf!(@text $t.italic($f); $($rest)*)
+    };
+    (@text $t:expr; code $f:tt $($rest:tt)*) => {
+        $crate::durf!(@text $t.code($f); $($rest)*)
+    };
+    (@text $t:expr; a($link:expr) $f:tt $($rest:tt)*) => {
+        $crate::durf!(@text $t.link($f, $link); $($rest)*)
+    };
+    (@text $t:expr; $f:tt $($rest:tt)*) => {
+        $crate::durf!(@text $t.text($f); $($rest)*)
+    };
+
+    ($($nodes:tt)*) => {
+        $crate::durf!(@nodes $crate::AstBuilder::new(); $($nodes)*).build()
+    };
+}
@@ -0,0 +1,163 @@
+use super::*;
+
+use std::borrow::Cow;
+use unicode_normalization::UnicodeNormalization;
+
+/// How text is cleaned after parsing.
+/// Code blocks are never cleaned.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CleanFlags {
+    /// Unicode normalization form, if any.
+    pub normalization: Option<NormalizationForm>,
+    /// Handling of the ideographic space (U+3000), often used to indent
+    /// Japanese prose.
+    pub fullwidth_space: SpaceMode,
+    /// Remove zero-width spaces (U+200B), word joiners (U+2060), and byte
+    /// order marks (U+FEFF).
+    pub remove_zero_width: bool,
+    /// Remove zero-width joiners (U+200D) and non-joiners (U+200C).
+    /// These shape emoji sequences and scripts like Arabic and Devanagari,
+    /// so they are kept by default.
+    pub remove_joiners: bool,
+    /// Remove soft hyphens (U+00AD), which only mark possible line breaks.
+    pub remove_soft_hyphens: bool,
+    /// Replace smart quotes with ASCII quotes and ellipses with `...`.
+    pub ascii_punctuation: bool,
+}
+
+impl Default for CleanFlags {
+    fn default() -> Self {
+        Self {
+            normalization: None,
+            fullwidth_space: SpaceMode::Keep,
+            remove_zero_width: true,
+            remove_joiners: false,
+            remove_soft_hyphens: true,
+            ascii_punctuation: false,
+        }
+    }
+}
+
+/// A Unicode normalization form.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NormalizationForm {
+    /// Canonical composition.
+    #[serde(alias = "nfc")]
+    Nfc,
+    /// Canonical decomposition.
+    #[serde(alias = "nfd")]
+    Nfd,
+    /// Compatibility composition, which also folds full-width letters and
+    /// half-width katakana.
+    #[serde(alias = "nfkc")]
+    Nfkc,
+    /// Compatibility decomposition.
+    #[serde(alias = "nfkd")]
+    Nfkd,
+}
+
+/// Handling of a kind of space.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpaceMode {
+    /// Keep the space.
+    #[serde(alias = "keep")]
+    Keep,
+    /// Replace the space with an ASCII space.
+    #[serde(alias = "ascii")]
+    Ascii,
+    /// Remove the space.
+    #[serde(alias = "remove")]
+    Remove,
+}
+
+impl CleanFlags {
+    /// Check if cleaning leaves all text unchanged.
+    pub fn is_noop(&self) -> bool {
+        self.normalization.is_none()
+            && self.fullwidth_space == SpaceMode::Keep
+            && !self.remove_zero_width
+            && !self.remove_joiners
+            && !self.remove_soft_hyphens
+            && !self.ascii_punctuation
+    }
+
+    /// Clean text, returning it unchanged, without allocating, if there is
+    /// nothing to clean.
+    /// Characters are replaced before normalizing, since compatibility forms
+    /// fold the ideographic space into an ASCII space.
+    pub fn clean<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let text = match text.chars().any(|c| self.replacement(c).is_some()) {
+            true => {
+                let mut cleaned = String::with_capacity(text.len());
+                for c in text.chars() {
+                    match self.replacement(c) {
+                        Some(replacement) => cleaned.push_str(replacement),
+                        None => cleaned.push(c),
+                    }
+                }
+                Cow::Owned(cleaned)
+            }
+            false => Cow::Borrowed(text),
+        };
+        match self.normalization {
+            None => text,
+            Some(NormalizationForm::Nfc) => Cow::Owned(text.nfc().collect()),
+            Some(NormalizationForm::Nfd) => Cow::Owned(text.nfd().collect()),
+            Some(NormalizationForm::Nfkc) => Cow::Owned(text.nfkc().collect()),
+            Some(NormalizationForm::Nfkd) => Cow::Owned(text.nfkd().collect()),
+        }
+    }
+
+    /// Replacement of a character, if it is cleaned.
+    fn replacement(&self, c: char) -> Option<&'static str> {
+        match c {
+            '\u{3000}' => match self.fullwidth_space {
+                SpaceMode::Keep => None,
+                SpaceMode::Ascii => Some(" "),
+                SpaceMode::Remove => Some(""),
+            },
+            '\u{200B}' | '\u{2060}' | '\u{FEFF}' if self.remove_zero_width => Some(""),
+            '\u{200C}' | '\u{200D}' if self.remove_joiners => Some(""),
+            '\u{AD}' if self.remove_soft_hyphens => Some(""),
+            '‘' | '’' | '‚' | '‛' if self.ascii_punctuation => Some("'"),
+            '“' | '”' | '„' | '‟' if self.ascii_punctuation => Some("\""),
+            '…' if self.ascii_punctuation => Some("..."),
+            _ => None,
+        }
+    }
+}
+
+impl Text {
+    /// Clean the text of each fragment.
+    pub fn clean(&mut self, flags: &CleanFlags) {
+        for frag in &mut self.fragments {
+            if let Cow::Owned(cleaned) = flags.clean(&frag.text) {
+                frag.text = cleaned;
+            }
+        }
+    }
+}
+
+impl RawNode {
+    /// Clean all text of the node and its descendents, except code.
+    pub fn clean(&mut self, flags: &CleanFlags) {
+        if flags.is_noop() {
+            return;
+        }
+        match self {
+            Self::Code(_) => return,
+            Self::Text(text) => text.clean(flags),
+            Self::Section(section) => {
+                if let Some(title) = &mut section.title {
+                    title.clean(flags);
+                }
+            }
+            Self::Details(details) => details.summary.clean(flags),
+            Self::Empty | Self::Form(_) | Self::Field(_) => {}
+        }
+        for node in self.children_mut() {
+            node.clean(flags);
+        }
+    }
+}
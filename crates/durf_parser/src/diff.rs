@@ -0,0 +1,309 @@
+//! Structural differences between ASTs.
+//!
+//! Children are aligned by their longest common subsequence of equal nodes,
+//! found in linear space.
+//! Unmatched nodes of the same type are paired as changes, descending into
+//! containers, and the rest are inserted or removed.
+
+use super::*;
+
+/// Differences between two ASTs.
+#[derive(Clone, Debug, Default)]
+pub struct AstDiff {
+    /// Nodes only in the new AST, by their path in the new AST.
+    pub inserted: Vec<(NodePath, Node)>,
+    /// Nodes only in the old AST, by their path in the old AST.
+    pub removed: Vec<(NodePath, Node)>,
+    /// Nodes changed in place.
+    pub changed: Vec<NodeChange>,
+}
+
+/// A node changed between two ASTs.
+/// Changes to the children of containers are listed separately.
+#[derive(Clone, Debug)]
+pub struct NodeChange {
+    pub old_path: NodePath,
+    pub new_path: NodePath,
+    pub old: Node,
+    pub new: Node,
+    /// Edits to the text of the node, or the title or summary of a
+    /// container. Empty if the text is unchanged.
+    pub text: Vec<TextEdit>,
+}
+
+/// A run of text in a text diff.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    pub kind: EditKind,
+    pub text: String,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EditKind {
+    Equal,
+    Inserted,
+    Removed,
+}
+
+impl AstDiff {
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Compare two nodes at their paths.
+    fn node(&mut self, old_path: NodePath, old: &Node, new_path: NodePath, new: &Node) {
+        if old == new {
+            return;
+        }
+        let (old_raw, new_raw) = (&**old, &**new);
+        let containers = matches!(
+            (old_raw, new_raw),
+            (RawNode::Section(_), RawNode::Section(_))
+                | (RawNode::Details(_), RawNode::Details(_))
+                | (RawNode::Form(_), RawNode::Form(_))
+        );
+        if !containers || !same_header(old_raw, new_raw) {
+            let (old_text, new_text) = (own_text(old_raw), own_text(new_raw));
+            let text = match old_text == new_text {
+                true => Vec::new(),
+                false => text_diff(&old_text, &new_text),
+            };
+            self.changed.push(NodeChange {
+                old_path: old_path.clone(),
+                new_path: new_path.clone(),
+                old: old.clone(),
+                new: new.clone(),
+                text,
+            });
+        }
+        if containers {
+            self.nodes(&old_path, old_raw.children(), &new_path, new_raw.children());
+        }
+    }
+
+    /// Compare the children of two containers.
+    fn nodes(&mut self, old_path: &NodePath, old: &[Node], new_path: &NodePath, new: &[Node]) {
+        let matched = lcs(old, new, |a, b| a == b);
+        let (mut i, mut j) = (0, 0);
+        for (next_i, next_j) in matched.into_iter().chain([(old.len(), new.len())]) {
+            // Pair unmatched nodes of the same type, in order.
+            let mut pending = j..next_j;
+            for (old_index, old_node) in old.iter().enumerate().take(next_i).skip(i) {
+                let kind = std::mem::discriminant(&**old_node);
+                match pending
+                    .clone()
+                    .find(|n| std::mem::discriminant(&*new[*n]) == kind)
+                {
+                    Some(new_index) => {
+                        self.insert(new_path, new, pending.start..new_index);
+                        self.node(
+                            old_path.child(old_index),
+                            old_node,
+                            new_path.child(new_index),
+                            &new[new_index],
+                        );
+                        pending.start = new_index + 1;
+                    }
+                    None => self
+                        .removed
+                        .push((old_path.child(old_index), old_node.clone())),
+                }
+            }
+            self.insert(new_path, new, pending);
+            (i, j) = (next_i + 1, next_j + 1);
+        }
+    }
+
+    /// Record a range of new children as inserted.
+    fn insert(&mut self, path: &NodePath, nodes: &[Node], range: std::ops::Range<usize>) {
+        for (index, node) in nodes.iter().enumerate().take(range.end).skip(range.start) {
+            self.inserted.push((path.child(index), node.clone()));
+        }
+    }
+}
+
+impl Ast {
+    /// Find the nodes inserted, removed, and changed from one AST to another.
+    pub fn diff(old: &Ast, new: &Ast) -> AstDiff {
+        let mut diff = AstDiff::default();
+        diff.node(NodePath::root(), &old.root, NodePath::root(), &new.root);
+        diff
+    }
+}
+
+/// Check if two containers are equal, other than their children.
+fn same_header(old: &RawNode, new: &RawNode) -> bool {
+    match (old, new) {
+        (RawNode::Section(a), RawNode::Section(b)) => {
+            a.title == b.title
+                && a.ordering == b.ordering
+                && a.kind == b.kind
+                && a.lang == b.lang
+                && a.dir == b.dir
+        }
+        (RawNode::Details(a), RawNode::Details(b)) => a.summary == b.summary && a.open == b.open,
+        (RawNode::Form(a), RawNode::Form(b)) => {
+            a.action == b.action && a.method == b.method && a.enctype == b.enctype
+        }
+        _ => false,
+    }
+}
+
+/// Text of a node, without its children.
+fn own_text(node: &RawNode) -> String {
+    match node {
+        RawNode::Text(text) => text.collect(),
+        RawNode::Code(code) => code.text.collect(),
+        RawNode::Section(section) => section
+            .title
+            .as_ref()
+            .map(Text::collect)
+            .unwrap_or_default(),
+        RawNode::Details(details) => details.summary.collect(),
+        RawNode::Field(field) => field.value.clone(),
+        RawNode::Empty | RawNode::Form(_) => String::new(),
+    }
+}
+
+/// Diff text by words, whitespace, and other characters, so text without
+/// spaces, like Japanese, is compared by character.
+fn text_diff(old: &str, new: &str) -> Vec<TextEdit> {
+    let (old, new) = (tokens(old), tokens(new));
+    let mut edits: Vec<TextEdit> = Vec::new();
+    let mut push = |kind: EditKind, text: &str| match edits.last_mut() {
+        Some(last) if last.kind == kind => last.text.push_str(text),
+        _ => edits.push(TextEdit {
+            kind,
+            text: text.into(),
+        }),
+    };
+
+    let (mut i, mut j) = (0, 0);
+    for (next_i, next_j) in lcs(&old, &new, |a, b| a == b)
+        .into_iter()
+        .chain([(old.len(), new.len())])
+    {
+        for token in &old[i..next_i] {
+            push(EditKind::Removed, token);
+        }
+        for token in &new[j..next_j] {
+            push(EditKind::Inserted, token);
+        }
+        if let Some(token) = old.get(next_i) {
+            push(EditKind::Equal, token);
+        }
+        (i, j) = (next_i + 1, next_j + 1);
+    }
+    edits
+}
+
+/// Split text into runs of whitespace, runs of ASCII letters and digits,
+/// and single other characters.
+fn tokens(text: &str) -> Vec<&str> {
+    let class = |c: char| match c {
+        c if c.is_whitespace() => 1,
+        c if c.is_ascii_alphanumeric() => 2,
+        _ => 0,
+    };
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut previous = None;
+    for (index, c) in text.char_indices() {
+        let current = class(c);
+        if index > start && (current == 0 || previous != Some(current)) {
+            tokens.push(&text[start..index]);
+            start = index;
+        }
+        previous = Some(current);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// Most cells compared by [`lcs`] before the middle of two sequences is
+/// treated as entirely removed and inserted, to bound the time of diffing
+/// large, mostly different nodes.
+const MAX_LCS_CELLS: usize = 1 << 24;
+
+/// Longest common subsequence of two sequences, as pairs of indices.
+fn lcs<T>(a: &[T], b: &[T], eq: impl Fn(&T, &T) -> bool) -> Vec<(usize, usize)> {
+    // Most edits are small, so skip the common ends.
+    let prefix = a.iter().zip(b).take_while(|(x, y)| eq(x, y)).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| eq(x, y))
+        .count();
+    let (middle_a, middle_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut pairs: Vec<(usize, usize)> = (0..prefix).map(|k| (k, k)).collect();
+    match middle_a.len().saturating_mul(middle_b.len()) {
+        cells if cells > MAX_LCS_CELLS => {
+            tracing::debug!("Skipping LCS of {cells} cells");
+        }
+        _ => hirschberg(middle_a, middle_b, (prefix, prefix), &eq, &mut pairs),
+    }
+    pairs.extend((0..suffix).map(|k| (a.len() - suffix + k, b.len() - suffix + k)));
+    pairs
+}
+
+/// Hirschberg's algorithm, finding the longest common subsequence in space
+/// linear in the length of the sequences, by splitting `a` in half and `b`
+/// where the subsequences of the halves are longest.
+fn hirschberg<T>(
+    a: &[T],
+    b: &[T],
+    offset: (usize, usize),
+    eq: &impl Fn(&T, &T) -> bool,
+    pairs: &mut Vec<(usize, usize)>,
+) {
+    if a.is_empty() || b.is_empty() {
+        return;
+    }
+    if let [x] = a {
+        if let Some(j) = b.iter().position(|y| eq(x, y)) {
+            pairs.push((offset.0, offset.1 + j));
+        }
+        return;
+    }
+
+    let mid = a.len() / 2;
+    let forward = lcs_lengths(mid, b.len(), |i, j| eq(&a[i], &b[j]));
+    let backward = lcs_lengths(a.len() - mid, b.len(), |i, j| {
+        eq(&a[a.len() - 1 - i], &b[b.len() - 1 - j])
+    });
+    let split = (0..=b.len())
+        .max_by_key(|&j| (forward[j] + backward[b.len() - j], std::cmp::Reverse(j)))
+        .unwrap_or_default();
+
+    hirschberg(&a[..mid], &b[..split], offset, eq, pairs);
+    hirschberg(
+        &a[mid..],
+        &b[split..],
+        (offset.0 + mid, offset.1 + split),
+        eq,
+        pairs,
+    );
+}
+
+/// Lengths of the longest common subsequences of the first `n` items of one
+/// sequence and each prefix of the first `m` items of another.
+fn lcs_lengths(n: usize, m: usize, eq: impl Fn(usize, usize) -> bool) -> Vec<u32> {
+    let mut lengths = vec![0u32; m + 1];
+    for i in 0..n {
+        // Length for the previous row and column.
+        let mut diagonal = 0;
+        for j in 0..m {
+            let above = lengths[j + 1];
+            lengths[j + 1] = match eq(i, j) {
+                true => diagonal + 1,
+                false => above.max(lengths[j]),
+            };
+            diagonal = above;
+        }
+    }
+    lengths
+}
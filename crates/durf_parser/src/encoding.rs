@@ -0,0 +1,221 @@
+//! Character encoding detection, following the WHATWG encoding sniffing
+//! algorithm.
+
+use super::*;
+
+use encoding_rs::Encoding;
+
+/// Number of bytes scanned for a `<meta>` charset declaration.
+pub(crate) const PRESCAN_BYTES: usize = 1024;
+
+/// The character encoding a document was decoded with.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetectedEncoding {
+    /// Canonical name of the encoding, like `Shift_JIS`.
+    pub name: String,
+    /// Where the encoding came from.
+    pub source: EncodingSource,
+    /// Whether malformed input was replaced with U+FFFD.
+    pub had_errors: bool,
+}
+
+/// Source of a detected encoding, from highest to lowest priority.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EncodingSource {
+    /// A byte order mark.
+    Bom,
+    /// The `charset` parameter of the content type.
+    ContentType,
+    /// A `<meta charset>` or `<meta http-equiv="content-type">` declaration.
+    Meta,
+    /// No declaration; UTF-8 if the input is valid UTF-8, otherwise
+    /// windows-1252.
+    Default,
+}
+
+/// Decode a document, returning the text and the encoding used.
+pub(crate) fn decode(
+    document: &[u8],
+    content_type: Option<&str>,
+) -> Result<(String, DetectedEncoding), Error> {
+    let (encoding, source) = detect(document, content_type)?;
+
+    // The BOM is removed if it matches, and overrides the encoding if not.
+    let (text, encoding, had_errors) = encoding.decode(document);
+    tracing::debug!("Decoded document as {} from {source:?}", encoding.name());
+    Ok((
+        text.into_owned(),
+        DetectedEncoding {
+            name: encoding.name().into(),
+            source,
+            had_errors,
+        },
+    ))
+}
+
+/// Detect the encoding of a stream from its first bytes, returning a
+/// decoder for the whole stream, starting with those bytes.
+pub(crate) fn decoder(
+    head: &[u8],
+    content_type: Option<&str>,
+) -> Result<(encoding_rs::Decoder, DetectedEncoding), Error> {
+    let (encoding, source) = detect(head, content_type)?;
+    tracing::debug!("Decoding stream as {} from {source:?}", encoding.name());
+    Ok((
+        // The BOM is removed if it matches, and overrides the encoding if not.
+        encoding.new_decoder(),
+        DetectedEncoding {
+            name: encoding.name().into(),
+            source,
+            had_errors: false,
+        },
+    ))
+}
+
+/// Check if the content type declares a supported encoding, so only a byte
+/// order mark can override it.
+pub(crate) fn is_declared(content_type: Option<&str>) -> bool {
+    content_type
+        .and_then(charset_param)
+        .and_then(for_label)
+        .is_some()
+}
+
+/// Find the encoding of a document and its source.
+fn detect(
+    document: &[u8],
+    content_type: Option<&str>,
+) -> Result<(&'static Encoding, EncodingSource), Error> {
+    let (encoding, source, label) = sniff(document, content_type);
+    // Labels like `iso-2022-kr` map to the replacement encoding, which
+    // would decode the whole document as a single U+FFFD.
+    if encoding == encoding_rs::REPLACEMENT {
+        return Err(Error::Encoding {
+            encoding: label,
+            offset: Some(0),
+        });
+    }
+    Ok((encoding, source))
+}
+
+/// Find the encoding of a document, with its source and the label it was
+/// declared with.
+fn sniff(
+    document: &[u8],
+    content_type: Option<&str>,
+) -> (&'static Encoding, EncodingSource, String) {
+    if let Some((encoding, _)) = Encoding::for_bom(document) {
+        return (encoding, EncodingSource::Bom, encoding.name().into());
+    }
+    if let Some((encoding, label)) = content_type.and_then(charset_param).and_then(for_label) {
+        return (encoding, EncodingSource::ContentType, label);
+    }
+    if let Some((encoding, label)) = prescan(&document[..document.len().min(PRESCAN_BYTES)]) {
+        return (encoding, EncodingSource::Meta, label);
+    }
+    // A document cut off within a character is still UTF-8.
+    let encoding = match std::str::from_utf8(document) {
+        Ok(_) => encoding_rs::UTF_8,
+        Err(e) if e.error_len().is_none() => encoding_rs::UTF_8,
+        Err(_) => encoding_rs::WINDOWS_1252,
+    };
+    (encoding, EncodingSource::Default, encoding.name().into())
+}
+
+/// Get an encoding from a label, keeping the trimmed label.
+fn for_label(label: &str) -> Option<(&'static Encoding, String)> {
+    let label = label.trim().trim_matches(['"', '\'']);
+    Encoding::for_label(label.as_bytes()).map(|encoding| (encoding, label.into()))
+}
+
+/// Get the `charset` parameter of a content type, like
+/// `text/html; charset=shift_jis`.
+fn charset_param(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim().eq_ignore_ascii_case("charset").then_some(value)
+    })
+}
+
+/// Scan the start of a document for a `<meta>` charset declaration.
+fn prescan(head: &[u8]) -> Option<(&'static Encoding, String)> {
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
+    let mut rest = head.as_str();
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let end = rest.find('>').unwrap_or(rest.len());
+        let tag = &rest[1..end];
+        rest = &rest[end..];
+        let Some(attributes) = tag
+            .strip_prefix("meta")
+            .filter(|a| a.starts_with(|c: char| c.is_ascii_whitespace() || c == '/'))
+        else {
+            continue;
+        };
+
+        let attributes = meta_attributes(attributes);
+        let value = |name: &str| attributes.iter().find(|(n, _)| *n == name).map(|(_, v)| *v);
+        let label = match value("charset") {
+            Some(charset) => charset,
+            None if value("http-equiv") == Some("content-type") => {
+                match value("content").and_then(charset_param) {
+                    Some(charset) => charset,
+                    None => continue,
+                }
+            }
+            None => continue,
+        };
+        let Some((encoding, label)) = for_label(label) else {
+            continue;
+        };
+        // A declared UTF-16 document would not have ASCII-compatible markup,
+        // so the declaration is wrong.
+        let encoding = match encoding {
+            e if e == encoding_rs::UTF_16LE || e == encoding_rs::UTF_16BE => encoding_rs::UTF_8,
+            e if e == encoding_rs::X_USER_DEFINED => encoding_rs::WINDOWS_1252,
+            e => e,
+        };
+        return Some((encoding, label));
+    }
+    None
+}
+
+/// Split the attributes of a tag into names and unquoted values.
+/// Attributes without values are ignored.
+fn meta_attributes(mut attributes: &str) -> Vec<(&str, &str)> {
+    let mut parsed = Vec::new();
+    loop {
+        attributes = attributes.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+        let name_end = attributes
+            .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '/')
+            .unwrap_or(attributes.len());
+        if name_end == 0 {
+            return parsed;
+        }
+        let name = &attributes[..name_end];
+        attributes = attributes[name_end..].trim_start();
+        let Some(value) = attributes.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let (value, rest) = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = &value[1..];
+                let end = value.find(quote).unwrap_or(value.len());
+                (&value[..end], value.get(end + 1..).unwrap_or_default())
+            }
+            _ => {
+                let end = value
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or(value.len());
+                (&value[..end], &value[end..])
+            }
+        };
+        parsed.push((name, value));
+        attributes = rest;
+    }
+}
@@ -1,16 +1,64 @@
 /// durf error type.
-#[derive(Copy, Clone, Debug)]
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Depth exceeded during parse.
-    DepthExceeded,
-    /// Feature is incomplete.
-    Todo,
+    #[error("depth exceeded at <{element}> ({path})")]
+    DepthExceeded {
+        /// Name of the element that was too deep.
+        element: String,
+        /// Path to the element, like `html > body > p`.
+        path: String,
+    },
+    /// An element that the parser does not support.
+    #[error("unsupported element <{element}> ({path})")]
+    UnsupportedElement { element: String, path: String },
+    /// A parse rule that can never match.
+    #[error("invalid rule {rule}: {reason}")]
+    InvalidRule { rule: String, reason: String },
+    /// A search query that cannot be compiled.
+    #[error("invalid query `{query}`: {reason}")]
+    InvalidQuery { query: String, reason: String },
+    /// An input format that cannot be parsed.
+    #[error("unsupported format `{format}`")]
+    UnsupportedFormat { format: String },
+    /// Input that could not be decoded.
+    #[error("failed to decode input as {encoding}{}", offset.map(|o| format!(" at byte {o}")).unwrap_or_default())]
+    Encoding {
+        encoding: String,
+        /// Byte offset of the first undecodable input, if known.
+        offset: Option<usize>,
+    },
+    /// A resource limit was reached.
+    #[error("{limit} limit of {max} exceeded ({path})")]
+    ResourceLimit {
+        limit: crate::Limit,
+        max: usize,
+        path: String,
+    },
+    /// An error with additional context.
+    #[error("{context}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<Error>,
+    },
 }
 
-impl std::error::Error for Error {}
+impl Error {
+    /// Wrap the error with context.
+    pub fn context(self, context: impl Into<String>) -> Self {
+        Self::Context {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "DurfError {:?}", self)
+    /// The innermost error, without context.
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Self::Context { source, .. } => source.root_cause(),
+            _ => self,
+        }
     }
 }
@@ -0,0 +1,189 @@
+//! Bundled grammars.
+
+use super::*;
+
+/// All bundled grammars.
+pub(crate) static GRAMMARS: &[&Grammar] = &[
+    &RUST,
+    &PYTHON,
+    &SHELL,
+    &JSON,
+    &TOML,
+    &HTML,
+    &CSS,
+    &JAVASCRIPT,
+];
+
+pub(crate) static RUST: Grammar = Grammar {
+    names: &["rust", "rs"],
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while", "yield",
+    ],
+    types: &[
+        "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32",
+        "i64", "i128", "isize", "f32", "f64",
+    ],
+    constants: &["true", "false", "None", "Some", "Ok", "Err"],
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", "\"", true)],
+    lifetimes: true,
+    macros: true,
+    capitalized_types: true,
+    ..Grammar::EMPTY
+};
+
+pub(crate) static PYTHON: Grammar = Grammar {
+    names: &["python", "py", "python3"],
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is",
+        "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with",
+        "yield", "match", "case",
+    ],
+    types: &[
+        "int", "float", "str", "bytes", "bool", "list", "dict", "set", "tuple", "object",
+    ],
+    constants: &["True", "False", "None", "self", "cls"],
+    line_comments: &["#"],
+    strings: &[
+        ("\"\"\"", "\"\"\"", true),
+        ("'''", "'''", true),
+        ("\"", "\"", true),
+        ("'", "'", true),
+    ],
+    at_class: Some(TokenClass::Function),
+    capitalized_types: true,
+    ..Grammar::EMPTY
+};
+
+pub(crate) static SHELL: Grammar = Grammar {
+    names: &["shell", "sh", "bash", "zsh", "console", "shell-session"],
+    keywords: &[
+        "if", "then", "else", "elif", "fi", "case", "esac", "for", "while", "until", "do", "done",
+        "in", "function", "select", "return", "local", "export", "readonly",
+    ],
+    constants: &["true", "false"],
+    line_comments: &["#"],
+    strings: &[("\"", "\"", true), ("'", "'", false), ("`", "`", true)],
+    ident_chars: "-./",
+    variables: true,
+    ..Grammar::EMPTY
+};
+
+pub(crate) static JSON: Grammar = Grammar {
+    names: &["json", "jsonc", "json5"],
+    constants: &["true", "false", "null"],
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", "\"", true)],
+    string_keys: true,
+    ..Grammar::EMPTY
+};
+
+pub(crate) static TOML: Grammar = Grammar {
+    names: &["toml"],
+    constants: &["true", "false", "inf", "nan"],
+    line_comments: &["#"],
+    strings: &[
+        ("\"\"\"", "\"\"\"", true),
+        ("'''", "'''", false),
+        ("\"", "\"", true),
+        ("'", "'", false),
+    ],
+    ident_chars: "-.",
+    keys: KeyStyle::Equals,
+    string_keys: true,
+    table_headers: true,
+    ..Grammar::EMPTY
+};
+
+pub(crate) static HTML: Grammar = Grammar {
+    names: &["html", "xml", "xhtml", "svg"],
+    markup: true,
+    ..Grammar::EMPTY
+};
+
+pub(crate) static CSS: Grammar = Grammar {
+    names: &["css", "scss", "less"],
+    constants: &["inherit", "initial", "unset", "none", "auto"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", "\"", true), ("'", "'", true)],
+    ident_chars: "-",
+    keys: KeyStyle::Colon,
+    at_class: Some(TokenClass::Keyword),
+    selectors: true,
+    ..Grammar::EMPTY
+};
+
+pub(crate) static JAVASCRIPT: Grammar = Grammar {
+    names: &[
+        "javascript",
+        "js",
+        "jsx",
+        "mjs",
+        "cjs",
+        "typescript",
+        "ts",
+        "tsx",
+    ],
+    keywords: &[
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "debugger",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "export",
+        "extends",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "import",
+        "in",
+        "instanceof",
+        "let",
+        "new",
+        "of",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "try",
+        "typeof",
+        "var",
+        "void",
+        "while",
+        "with",
+        "yield",
+        "interface",
+        "type",
+        "enum",
+        "implements",
+    ],
+    types: &[
+        "number", "string", "boolean", "any", "unknown", "never", "object",
+    ],
+    constants: &["true", "false", "null", "undefined", "NaN", "Infinity"],
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", "\"", true), ("'", "'", true), ("`", "`", true)],
+    ident_chars: "$",
+    at_class: Some(TokenClass::Function),
+    capitalized_types: true,
+    ..Grammar::EMPTY
+};
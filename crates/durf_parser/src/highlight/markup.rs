@@ -0,0 +1,103 @@
+//! Markup lexing, for HTML and XML.
+
+use super::*;
+
+/// Split markup into classified tokens.
+pub(crate) fn tokenize(code: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < code.len() {
+        let rest = &code[pos..];
+        let (len, class) = if rest.starts_with("<!--") {
+            let len = rest.find("-->").map_or(rest.len(), |i| i + 3);
+            (len, Some(TokenClass::Comment))
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            let len = rest.find('>').map_or(rest.len(), |i| i + 1);
+            (len, Some(TokenClass::Keyword))
+        } else if rest.starts_with('<')
+            && rest[1..].starts_with(|c: char| c.is_alphabetic() || c == '/')
+        {
+            pos = tag(code, pos, &mut tokens);
+            continue;
+        } else if let Some(len) = entity_len(rest) {
+            (len, Some(TokenClass::Constant))
+        } else {
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let len = rest[first..]
+                .find(['<', '&'])
+                .map_or(rest.len(), |i| first + i);
+            (len, None)
+        };
+        tokens.push((pos..pos + len, class));
+        pos += len;
+    }
+    tokens
+}
+
+/// Length of a character entity, like `&amp;`, at the start of markup.
+fn entity_len(rest: &str) -> Option<usize> {
+    if !rest.starts_with('&') {
+        return None;
+    }
+    let end = rest.find(';')?;
+    let name = &rest[1..end];
+    let valid = end > 1 && end <= 10 && name.chars().all(|c| c.is_alphanumeric() || c == '#');
+    valid.then_some(end + 1)
+}
+
+/// Lex a tag and its attributes, returning the position after the tag.
+fn tag(code: &str, mut pos: usize, tokens: &mut Vec<Token>) -> usize {
+    // Opening bracket and name.
+    let open = match code[pos..].starts_with("</") {
+        true => 2,
+        false => 1,
+    };
+    tokens.push((pos..pos + open, Some(TokenClass::Punctuation)));
+    pos += open;
+    let rest = &code[pos..];
+    let name = rest
+        .find(|c: char| !(c.is_alphanumeric() || "-_:".contains(c)))
+        .unwrap_or(rest.len());
+    tokens.push((pos..pos + name, Some(TokenClass::Tag)));
+    pos += name;
+
+    // Attributes until the tag closes.
+    let mut after_equals = false;
+    while pos < code.len() {
+        let rest = &code[pos..];
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+        let (len, class) = if c.is_whitespace() {
+            let len = rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len());
+            (len, None)
+        } else if rest.starts_with("/>") || c == '>' {
+            let len = if c == '>' { 1 } else { 2 };
+            tokens.push((pos..pos + len, Some(TokenClass::Punctuation)));
+            return pos + len;
+        } else if c == '=' {
+            after_equals = true;
+            (1, Some(TokenClass::Operator))
+        } else if c == '"' || c == '\'' {
+            after_equals = false;
+            let len = rest[1..].find(c).map_or(rest.len(), |i| i + 2);
+            (len, Some(TokenClass::String))
+        } else {
+            let len = rest
+                .find(|c: char| c.is_whitespace() || "=>/".contains(c))
+                .unwrap_or(rest.len())
+                .max(c.len_utf8());
+            let class = match after_equals {
+                true => TokenClass::String,
+                false => TokenClass::Attribute,
+            };
+            after_equals = false;
+            (len, Some(class))
+        };
+        tokens.push((pos..pos + len, class));
+        pos += len;
+    }
+    pos
+}
@@ -0,0 +1,432 @@
+//! Syntax highlighting for code blocks.
+//! Grammars are simple lexical descriptions compiled into the binary.
+
+use super::*;
+
+mod grammars;
+mod markup;
+
+/// Lexical description of a language.
+pub(crate) struct Grammar {
+    /// Language name and aliases.
+    pub(crate) names: &'static [&'static str],
+    pub(crate) keywords: &'static [&'static str],
+    pub(crate) types: &'static [&'static str],
+    pub(crate) constants: &'static [&'static str],
+    pub(crate) line_comments: &'static [&'static str],
+    pub(crate) block_comments: &'static [(&'static str, &'static str)],
+    /// String delimiters and whether backslash escapes apply, longest first.
+    pub(crate) strings: &'static [(&'static str, &'static str, bool)],
+    /// Characters allowed in identifiers besides alphanumerics and `_`.
+    pub(crate) ident_chars: &'static str,
+    /// How identifiers are recognized as keys.
+    pub(crate) keys: KeyStyle,
+    /// Strings followed by a key separator are keys.
+    pub(crate) string_keys: bool,
+    /// Class of `@name` tokens.
+    pub(crate) at_class: Option<TokenClass>,
+    /// `'a` is a lifetime unless it is closed like a char.
+    pub(crate) lifetimes: bool,
+    /// `name!` is a macro.
+    pub(crate) macros: bool,
+    /// Capitalized identifiers are types.
+    pub(crate) capitalized_types: bool,
+    /// `$name` and `${name}` are variables.
+    pub(crate) variables: bool,
+    /// `[name]` at the start of a line is a table header.
+    pub(crate) table_headers: bool,
+    /// Identifiers outside of braces are selectors.
+    pub(crate) selectors: bool,
+    /// The language is tag-based markup.
+    pub(crate) markup: bool,
+}
+
+impl Grammar {
+    /// Grammar that recognizes nothing, for building others.
+    pub(crate) const EMPTY: Grammar = Grammar {
+        names: &[],
+        keywords: &[],
+        types: &[],
+        constants: &[],
+        line_comments: &[],
+        block_comments: &[],
+        strings: &[],
+        ident_chars: "",
+        keys: KeyStyle::None,
+        string_keys: false,
+        at_class: None,
+        lifetimes: false,
+        macros: false,
+        capitalized_types: false,
+        variables: false,
+        table_headers: false,
+        selectors: false,
+        markup: false,
+    };
+
+    /// Find a bundled grammar by language name or alias.
+    pub(crate) fn find(language: &str) -> Option<&'static Grammar> {
+        let language = language.trim().to_ascii_lowercase();
+        grammars::GRAMMARS
+            .iter()
+            .copied()
+            .find(|g| g.names.contains(&language.as_str()))
+    }
+
+    /// Separator following a key.
+    fn key_separator(&self) -> char {
+        match self.keys {
+            KeyStyle::Equals => '=',
+            _ => ':',
+        }
+    }
+}
+
+/// How keys of mappings are recognized.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum KeyStyle {
+    None,
+    /// `key: value`, within braces.
+    Colon,
+    /// `key = value`, at the start of a line.
+    Equals,
+}
+
+/// A token as a byte range of the source and its class.
+pub(crate) type Token = (std::ops::Range<usize>, Option<TokenClass>);
+
+/// Check if a language has a bundled grammar.
+pub fn is_language_supported(language: &str) -> bool {
+    Grammar::find(language).is_some()
+}
+
+/// Split code into classified tokens.
+pub(crate) fn tokenize(code: &str, grammar: &Grammar) -> Vec<Token> {
+    if grammar.markup {
+        return markup::tokenize(code);
+    }
+
+    let mut lexer = Lexer {
+        code,
+        grammar,
+        pos: 0,
+        depth: 0,
+    };
+    let mut tokens = Vec::new();
+    while lexer.pos < code.len() {
+        let start = lexer.pos;
+        let class = lexer.next_token();
+        tokens.push((start..lexer.pos, class));
+    }
+    tokens
+}
+
+/// Replace the text of a code block with highlighted fragments.
+/// Returns false if the language is unknown or unsupported.
+pub(crate) fn highlight(code: &mut Code) -> bool {
+    let Some(grammar) = code.language().and_then(Grammar::find) else {
+        return false;
+    };
+
+    let source = code.text.collect();
+    let mut text = Text::new();
+    for (range, class) in tokenize(&source, grammar) {
+        match text.fragments.last_mut() {
+            Some(last) if last.attributes.token == class => last.text.push_str(&source[range]),
+            _ => {
+                let mut attributes = TextAttributes::new();
+                attributes.preformatted = true;
+                attributes.token = class;
+                text.append(TextFragment::new(&source[range], Some(attributes)));
+            }
+        }
+    }
+    code.text = text;
+    true
+}
+
+/// Characters lexed as operators.
+const OPERATORS: &str = "+-*/%=<>!&|^~?:";
+
+/// Keywords that introduce a function name.
+const DEFINERS: &[&str] = &["fn", "def", "function"];
+
+/// Lexer for code grammars.
+struct Lexer<'a> {
+    code: &'a str,
+    grammar: &'a Grammar,
+    /// Byte position in the code.
+    pos: usize,
+    /// Depth of curly braces.
+    depth: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn rest(&self) -> &'a str {
+        &self.code[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn eat_while(&mut self, f: impl Fn(char) -> bool) {
+        while let Some(c) = self.peek() {
+            if !f(c) {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn is_ident(&self, c: char) -> bool {
+        c.is_alphanumeric() || c == '_' || self.grammar.ident_chars.contains(c)
+    }
+
+    /// Next character after spaces on the current line.
+    fn next_significant(&self) -> Option<char> {
+        self.rest().trim_start_matches([' ', '\t']).chars().next()
+    }
+
+    /// Check if only whitespace precedes a position on its line.
+    fn at_line_start(&self, pos: usize) -> bool {
+        let line = self.code[..pos].rsplit('\n').next().unwrap_or_default();
+        line.trim().is_empty()
+    }
+
+    /// Check if a function definition keyword precedes a position.
+    fn follows_definer(&self, pos: usize) -> bool {
+        let before = self.code[..pos].trim_end_matches([' ', '\t']);
+        let word = before
+            .rsplit(|c: char| !self.is_ident(c))
+            .next()
+            .unwrap_or_default();
+        before.len() < pos && DEFINERS.contains(&word)
+    }
+
+    /// Check if a key separator follows, ignoring equality operators.
+    fn is_key(&self) -> bool {
+        let separator = self.grammar.key_separator();
+        let rest = self.rest().trim_start_matches([' ', '\t']);
+        let mut chars = rest.chars();
+        chars.next() == Some(separator) && chars.next() != Some(separator)
+    }
+
+    fn next_token(&mut self) -> Option<TokenClass> {
+        let grammar = self.grammar;
+        let rest = self.rest();
+        let c = self.peek()?;
+
+        if c.is_whitespace() {
+            self.eat_while(char::is_whitespace);
+            return None;
+        }
+
+        // Comments.
+        if grammar.line_comments.iter().any(|p| rest.starts_with(p)) {
+            self.pos += rest.find('\n').unwrap_or(rest.len());
+            return Some(TokenClass::Comment);
+        }
+        if let Some((open, close)) = grammar
+            .block_comments
+            .iter()
+            .find(|(o, _)| rest.starts_with(o))
+        {
+            self.pos += rest[open.len()..]
+                .find(close)
+                .map_or(rest.len(), |i| open.len() + i + close.len());
+            return Some(TokenClass::Comment);
+        }
+
+        // Strings.
+        if grammar.lifetimes && c == '\'' {
+            if let Some(class) = self.lifetime_or_char() {
+                return Some(class);
+            }
+        }
+        if let Some(&(open, close, escapes)) =
+            grammar.strings.iter().find(|(o, _, _)| rest.starts_with(o))
+        {
+            self.string(open, close, escapes);
+            if grammar.string_keys && self.is_key() {
+                return Some(TokenClass::Property);
+            }
+            return Some(TokenClass::String);
+        }
+
+        // Numbers.
+        let next_is_digit = rest[c.len_utf8()..].starts_with(|n: char| n.is_ascii_digit());
+        if c.is_ascii_digit() || (c == '.' && next_is_digit) {
+            self.bump();
+            self.eat_while(|c| c.is_alphanumeric() || c == '_' || c == '.');
+            return Some(TokenClass::Number);
+        }
+
+        // Prefixed names.
+        if grammar.variables && c == '$' {
+            self.variable();
+            return Some(TokenClass::Variable);
+        }
+        if let Some(class) = grammar.at_class {
+            if c == '@' && rest[1..].starts_with(|n: char| self.is_ident(n)) {
+                self.bump();
+                self.eat_while(|c| c.is_alphanumeric() || "_-.".contains(c));
+                return Some(class);
+            }
+        }
+        if grammar.selectors && c == '#' {
+            self.bump();
+            self.eat_while(|c| c.is_alphanumeric() || "_-".contains(c));
+            return match self.depth {
+                0 => Some(TokenClass::Tag),
+                _ => Some(TokenClass::Number),
+            };
+        }
+        if grammar.table_headers && c == '[' && self.at_line_start(self.pos) {
+            let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+            if let Some(end) = line.rfind(']') {
+                self.pos += end + 1;
+                return Some(TokenClass::Type);
+            }
+        }
+
+        if self.is_ident(c) {
+            return self.identifier();
+        }
+
+        // Punctuation and operators.
+        match c {
+            '{' => {
+                self.depth += 1;
+                self.bump();
+                Some(TokenClass::Punctuation)
+            }
+            '}' => {
+                self.depth = self.depth.saturating_sub(1);
+                self.bump();
+                Some(TokenClass::Punctuation)
+            }
+            '(' | ')' | '[' | ']' | ',' | ';' | '.' => {
+                self.bump();
+                Some(TokenClass::Punctuation)
+            }
+            c if OPERATORS.contains(c) => {
+                self.eat_while(|c| OPERATORS.contains(c));
+                Some(TokenClass::Operator)
+            }
+            _ => {
+                self.bump();
+                None
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> Option<TokenClass> {
+        let grammar = self.grammar;
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if !self.is_ident(c) {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+        let word = &self.code[start..self.pos];
+
+        if grammar.macros && self.rest().starts_with('!') && !self.rest().starts_with("!=") {
+            self.bump();
+            return Some(TokenClass::Function);
+        }
+        if grammar.keywords.contains(&word) {
+            return Some(TokenClass::Keyword);
+        }
+        if grammar.types.contains(&word) {
+            return Some(TokenClass::Type);
+        }
+        if grammar.constants.contains(&word) {
+            return Some(TokenClass::Constant);
+        }
+        if grammar.selectors && self.depth == 0 {
+            return Some(TokenClass::Tag);
+        }
+        let is_key = match grammar.keys {
+            KeyStyle::None => false,
+            KeyStyle::Colon => self.depth > 0 && self.is_key(),
+            KeyStyle::Equals => self.at_line_start(start) && self.is_key(),
+        };
+        if is_key {
+            return Some(TokenClass::Property);
+        }
+        if self.next_significant() == Some('(') || self.follows_definer(start) {
+            return Some(TokenClass::Function);
+        }
+        if grammar.capitalized_types && word.starts_with(|c: char| c.is_uppercase()) {
+            return Some(TokenClass::Type);
+        }
+        None
+    }
+
+    /// Lex a string, leaving the position after the closing delimiter.
+    fn string(&mut self, open: &str, close: &str, escapes: bool) {
+        self.pos += open.len();
+        while self.pos < self.code.len() {
+            let rest = self.rest();
+            if rest.starts_with(close) {
+                self.pos += close.len();
+                return;
+            }
+            if escapes && rest.starts_with('\\') {
+                self.bump();
+            }
+            self.bump();
+        }
+    }
+
+    /// Lex a char literal or a lifetime.
+    fn lifetime_or_char(&mut self) -> Option<TokenClass> {
+        let rest = &self.rest()[1..];
+        let mut chars = rest.chars();
+        let first = chars.next()?;
+
+        // Escaped char, like '\n' or '\u{1F600}'.
+        if first == '\\' {
+            let end = rest.char_indices().skip(2).find(|(_, c)| *c == '\'')?.0;
+            if end > 10 {
+                return None;
+            }
+            self.pos += 1 + end + 1;
+            return Some(TokenClass::String);
+        }
+        if chars.next() == Some('\'') {
+            self.pos += 1 + first.len_utf8() + 1;
+            return Some(TokenClass::String);
+        }
+        if first.is_alphabetic() || first == '_' {
+            self.bump();
+            self.eat_while(|c| c.is_alphanumeric() || c == '_');
+            return Some(TokenClass::Type);
+        }
+        None
+    }
+
+    /// Lex a shell variable.
+    fn variable(&mut self) {
+        self.bump();
+        match self.peek() {
+            Some('{') => {
+                let rest = self.rest();
+                self.pos += rest.find('}').map_or(rest.len(), |i| i + 1);
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                self.eat_while(|c| c.is_alphanumeric() || c == '_');
+            }
+            Some(c) if c.is_ascii_digit() || "@#?$!*-".contains(c) => self.bump(),
+            _ => {}
+        }
+    }
+}
@@ -1,10 +1,21 @@
 //! durf parser.
 
+mod arena;
 mod ast;
+mod builder;
+mod clean;
+mod diff;
+mod encoding;
 mod error;
+#[cfg(feature = "highlight")]
+mod highlight;
 mod nodes;
 mod parse;
 mod prelude;
+mod report;
+mod sanitize;
+mod search;
+mod stream;
 #[cfg(test)]
 mod tests;
 
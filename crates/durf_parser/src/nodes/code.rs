@@ -0,0 +1,75 @@
+use super::*;
+
+/// A block of code, with whitespace and line breaks preserved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Code {
+    /// Language of the code, if known.
+    pub language: Option<String>,
+    /// The code.
+    pub text: Text,
+}
+
+impl Code {
+    pub fn new(code: impl Into<String>, language: Option<String>) -> Self {
+        let mut attributes = TextAttributes::new();
+        attributes.preformatted = true;
+        let mut text = Text::new();
+        text.append(TextFragment::new(code, Some(attributes)));
+        Self { language, text }
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Split the code into fragments classified by syntax.
+    /// Returns false if the language is unknown or unsupported, or the
+    /// `highlight` feature is disabled.
+    pub fn highlight(&mut self) -> bool {
+        #[cfg(feature = "highlight")]
+        return crate::highlight::highlight(self);
+        #[cfg(not(feature = "highlight"))]
+        false
+    }
+
+    /// Get the language of a code element.
+    /// Uses `data-lang`, then `language-*` or `lang-*` classes.
+    pub(crate) fn language_from_element(
+        elem: &scraper::ElementRef,
+        flags: &mut ParseContext,
+    ) -> Option<String> {
+        if let Some(lang) = flags.attr(elem, "data-lang") {
+            return Some(lang.trim().to_ascii_lowercase());
+        }
+        flags
+            .attr(elem, "class")?
+            .split_ascii_whitespace()
+            .find_map(|c| c.strip_prefix("language-").or(c.strip_prefix("lang-")))
+            .filter(|lang| !lang.is_empty())
+            .map(|lang| lang.to_ascii_lowercase())
+    }
+
+    /// Collect the text of a code element, keeping whitespace.
+    /// Hidden descendants are skipped.
+    pub(crate) fn text_from_element(elem: &scraper::ElementRef, flags: &ParseFlags) -> String {
+        let mut code = String::new();
+        for node in elem.descendants() {
+            let hidden = std::iter::once(node)
+                .chain(node.ancestors())
+                .take_while(|n| n.id() != elem.id())
+                .filter_map(scraper::ElementRef::wrap)
+                .any(|e| flags.should_skip(&e) || flags.semantic(&e) == Some(&Semantic::Hidden));
+            if hidden {
+                continue;
+            }
+            if let Some(text) = node.value().as_text() {
+                code.push_str(text);
+            } else if let Some(sub_elem) = node.value().as_element() {
+                if sub_elem.name.local.as_ref() == "br" {
+                    code.push('\n');
+                }
+            }
+        }
+        code
+    }
+}
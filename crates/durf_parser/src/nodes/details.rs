@@ -0,0 +1,45 @@
+use super::*;
+
+/// A disclosure with a summary and collapsible content.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Details {
+    /// Summary shown whether or not the disclosure is open.
+    pub summary: Text,
+    /// Nodes revealed when the disclosure is open.
+    pub nodes: Vec<Node>,
+    /// Whether the disclosure is initially open.
+    pub open: bool,
+}
+
+impl Details {
+    pub fn new(summary: Text) -> Self {
+        Self {
+            summary,
+            nodes: Vec::new(),
+            open: false,
+        }
+    }
+
+    /// Set whether the disclosure is initially open.
+    pub fn with_open(mut self, open: bool) -> Self {
+        self.open = open;
+        self
+    }
+
+    pub fn summary(&self) -> &Text {
+        &self.summary
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        self.nodes.as_slice()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.summary.is_blank()
+            && self.nodes.iter().all(|n| match &**n {
+                RawNode::Empty => true,
+                RawNode::Section(s) => s.is_empty(),
+                _ => false,
+            })
+    }
+}
@@ -0,0 +1,243 @@
+use super::*;
+
+/// A form that may be submitted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Form {
+    /// URL the form submits to. None submits to the document.
+    pub action: Option<String>,
+    /// Method used to submit the form.
+    pub method: FormMethod,
+    /// Encoding of the submitted data.
+    pub enctype: String,
+    /// Fields, labels, and other content of the form.
+    pub nodes: Vec<Node>,
+}
+
+impl Form {
+    /// Default encoding of submitted data.
+    pub const DEFAULT_ENCTYPE: &'static str = "application/x-www-form-urlencoded";
+
+    pub fn new() -> Self {
+        Self {
+            action: None,
+            method: FormMethod::Get,
+            enctype: Self::DEFAULT_ENCTYPE.into(),
+            nodes: Vec::new(),
+        }
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        self.nodes.as_slice()
+    }
+
+    /// Collect fields of the form, recursively.
+    pub fn fields(&self) -> Vec<&Field> {
+        let mut fields = Vec::new();
+        let mut stack: Vec<&Node> = self.nodes.iter().rev().collect();
+        while let Some(node) = stack.pop() {
+            match &**node {
+                RawNode::Field(field) => fields.push(field),
+                other => stack.extend(other.children().iter().rev()),
+            }
+        }
+        fields
+    }
+
+    /// Name/value pairs submitted by default, without pressing a button.
+    pub fn data(&self) -> Vec<(String, String)> {
+        let mut data = Vec::new();
+        for field in self.fields() {
+            if field.disabled {
+                continue;
+            }
+            let Some(name) = &field.name else {
+                continue;
+            };
+            match &field.kind {
+                FieldKind::Submit | FieldKind::Button => {}
+                FieldKind::Checkbox | FieldKind::Radio => {
+                    if field.checked {
+                        data.push((name.clone(), field.value.clone()));
+                    }
+                }
+                FieldKind::Select(options) => {
+                    data.extend(
+                        options
+                            .iter()
+                            .filter(|o| o.selected)
+                            .map(|o| (name.clone(), o.value.clone())),
+                    );
+                }
+                _ => data.push((name.clone(), field.value.clone())),
+            }
+        }
+        data
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.iter().all(|n| n.is_empty())
+    }
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Method used to submit a form.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FormMethod {
+    #[default]
+    Get,
+    Post,
+    /// Closes a dialog, without submitting.
+    Dialog,
+}
+
+impl FormMethod {
+    /// Get the method from the `method` attribute.
+    pub fn from_attr(method: &str) -> Self {
+        match method.trim().to_ascii_lowercase().as_str() {
+            "post" => Self::Post,
+            "dialog" => Self::Dialog,
+            _ => Self::Get,
+        }
+    }
+}
+
+/// A form field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    /// Type of field.
+    pub kind: FieldKind,
+    /// Name the value is submitted as.
+    pub name: Option<String>,
+    /// Default value.
+    pub value: String,
+    /// Label of the field.
+    pub label: Option<String>,
+    /// Placeholder shown without a value.
+    pub placeholder: Option<String>,
+    /// Whether a checkbox or radio is checked.
+    pub checked: bool,
+    /// Whether the field is disabled.
+    pub disabled: bool,
+}
+
+impl Field {
+    pub fn new(kind: FieldKind) -> Self {
+        Self {
+            kind,
+            name: None,
+            value: String::new(),
+            label: None,
+            placeholder: None,
+            checked: false,
+            disabled: false,
+        }
+    }
+}
+
+/// Type of a form field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Single line text.
+    Text,
+    /// Search query.
+    Search,
+    /// Masked text.
+    Password,
+    /// Not shown, but submitted.
+    Hidden,
+    Checkbox,
+    Radio,
+    /// Button that submits the form.
+    Submit,
+    /// Button that does not submit the form.
+    Button,
+    /// Choice between options.
+    Select(Vec<SelectOption>),
+    /// Multi-line text.
+    TextArea,
+}
+
+impl FieldKind {
+    /// Get the kind from an input `type` attribute.
+    pub fn from_input_type(input_type: &str) -> Self {
+        match input_type.trim().to_ascii_lowercase().as_str() {
+            "search" => Self::Search,
+            "password" => Self::Password,
+            "hidden" => Self::Hidden,
+            "checkbox" => Self::Checkbox,
+            "radio" => Self::Radio,
+            "submit" | "image" => Self::Submit,
+            "button" | "reset" => Self::Button,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// An option of a select field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectOption {
+    /// Submitted value.
+    pub value: String,
+    /// Shown label.
+    pub label: String,
+    /// Whether the option is selected.
+    pub selected: bool,
+}
+
+/// Labels of the form fields in a document, by the id they reference.
+#[derive(Debug, Default)]
+pub(crate) struct FormLabels {
+    /// The first `label` element referencing each id. Its text is taken
+    /// when it is used, so it counts towards the text limits once.
+    labels: HashMap<String, ego_tree::NodeId>,
+    /// Ids of form fields.
+    fields: HashSet<String>,
+}
+
+impl FormLabels {
+    /// Element names of form fields.
+    pub(crate) const FIELDS: &[&str] = &["input", "select", "textarea", "button"];
+
+    /// Collect the labels of the document containing an element.
+    pub(crate) fn from_element(elem: &scraper::ElementRef) -> Self {
+        let mut labels = Self::default();
+        if let Some(root) = elem.ancestors().last() {
+            labels.extend(root.descendants());
+        }
+        labels
+    }
+
+    /// Collect the labels and fields among nodes.
+    pub(crate) fn extend<'a>(
+        &mut self,
+        nodes: impl IntoIterator<Item = ego_tree::NodeRef<'a, scraper::Node>>,
+    ) {
+        for e in nodes.into_iter().filter_map(scraper::ElementRef::wrap) {
+            let name = e.value().name.local.as_ref();
+            if name == "label" {
+                if let Some(id) = e.attr("for") {
+                    self.labels.entry(id.into()).or_insert(e.id());
+                }
+            } else if Self::FIELDS.contains(&name) {
+                if let Some(id) = e.attr("id") {
+                    self.fields.insert(id.into());
+                }
+            }
+        }
+    }
+
+    /// The label element referencing an id.
+    pub(crate) fn label(&self, id: &str) -> Option<ego_tree::NodeId> {
+        self.labels.get(id).copied()
+    }
+
+    /// Check if an id is of a form field.
+    pub(crate) fn is_field(&self, id: &str) -> bool {
+        self.fields.contains(id)
+    }
+}
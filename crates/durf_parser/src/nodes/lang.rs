@@ -0,0 +1,77 @@
+use super::*;
+
+/// Direction of text, from a `dir` attribute.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Direction {
+    /// Left to right.
+    Ltr,
+    /// Right to left.
+    Rtl,
+    /// Determined from the first strong character of the text.
+    Auto,
+}
+
+impl Direction {
+    /// Parse a `dir` attribute. Invalid values are ignored.
+    pub fn from_attr(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_ref() {
+            "ltr" => Some(Self::Ltr),
+            "rtl" => Some(Self::Rtl),
+            "auto" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+
+    /// Get the language and direction declared on an element, with the
+    /// language sanitized like text.
+    /// `bdi` isolates its text with an automatic direction by default.
+    pub(crate) fn from_element(
+        elem: &scraper::ElementRef,
+        flags: &mut ParseContext,
+    ) -> (Option<String>, Option<Self>) {
+        let lang = flags
+            .attr(elem, "lang")
+            .map(str::trim)
+            .filter(|lang| !lang.is_empty())
+            .map(|lang| sanitize(lang, &flags.sanitize).into_owned());
+        let dir = elem.attr("dir").and_then(Self::from_attr);
+        let dir = match elem.value().name.local.as_ref() {
+            "bdi" => dir.or(Some(Self::Auto)),
+            _ => dir,
+        };
+        (lang, dir)
+    }
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dir = match self {
+            Self::Ltr => "ltr",
+            Self::Rtl => "rtl",
+            Self::Auto => "auto",
+        };
+        write!(f, "{dir}")
+    }
+}
+
+impl Text {
+    /// Set the language and direction of fragments that do not have their
+    /// own.
+    pub fn set_language(&mut self, lang: Option<&Arc<str>>, dir: Option<Direction>) {
+        for frag in &mut self.fragments {
+            if frag.attributes.lang.is_none() {
+                frag.attributes.lang = lang.cloned();
+            }
+            frag.attributes.dir = frag.attributes.dir.or(dir);
+        }
+    }
+
+    /// Direction of the text, if every visible fragment has the same one.
+    pub fn direction(&self) -> Option<Direction> {
+        let mut visible = self.fragments.iter().filter(|f| !f.text.trim().is_empty());
+        let dir = visible.next()?.attributes.dir?;
+        visible
+            .all(|f| f.attributes.dir == Some(dir))
+            .then_some(dir)
+    }
+}
@@ -1,6 +1,6 @@
 #[allow(unused)]
 struct Media {
-    source: Box<Vec<u8>>,
+    source: Vec<u8>,
     media_type: MediaType,
 }
 
@@ -0,0 +1,185 @@
+use super::*;
+
+/// Options for minimizing an AST.
+/// Each step can be disabled to keep structure that callers rely on.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MinimizeOptions {
+    /// Remove sections without content.
+    pub remove_empty: bool,
+    /// Replace untitled, generic sets of one node with the node.
+    pub collapse_sets: bool,
+    /// Splice nested untitled, generic sets into their parent set.
+    /// Off by default, since it changes the position of nodes.
+    pub splice_sets: bool,
+    /// Replace untitled, generic lists of only one text with the text.
+    /// Off by default, since it turns the list into a paragraph.
+    pub hoist_text: bool,
+    /// Merge adjacent text fragments with equal attributes.
+    pub merge_fragments: bool,
+    /// Remove whitespace-only text between blocks, and whitespace-only
+    /// fragments at the edges of text.
+    pub drop_blank_text: bool,
+}
+
+impl Default for MinimizeOptions {
+    fn default() -> Self {
+        Self {
+            remove_empty: true,
+            collapse_sets: true,
+            splice_sets: false,
+            hoist_text: false,
+            merge_fragments: true,
+            drop_blank_text: true,
+        }
+    }
+}
+
+impl MinimizeOptions {
+    /// Only remove empty sections, keeping all other structure.
+    pub fn keep_structure() -> Self {
+        Self {
+            remove_empty: true,
+            collapse_sets: false,
+            splice_sets: false,
+            hoist_text: false,
+            merge_fragments: false,
+            drop_blank_text: false,
+        }
+    }
+}
+
+impl RawNode {
+    /// Minimize the node with the default options.
+    pub fn minimize(&mut self) {
+        self.minimize_with(&MinimizeOptions::default());
+    }
+
+    /// Minimize the node and its descendents.
+    pub fn minimize_with(&mut self, options: &MinimizeOptions) {
+        match self {
+            Self::Empty => {}
+            Self::Section(section) => {
+                if let Some(title) = &mut section.title {
+                    title.minimize(options);
+                }
+                Self::minimize_nodes(&mut section.nodes, options);
+
+                if options.splice_sets && section.is_plain_set() {
+                    section.splice_sets();
+                }
+                let hoist = match section.ordering {
+                    SectionOrdering::Set => options.collapse_sets,
+                    SectionOrdering::List | SectionOrdering::Enumeration => {
+                        options.hoist_text
+                            && matches!(section.nodes.first().map(|n| &**n), Some(Self::Text(_)))
+                    }
+                    SectionOrdering::Definition => false,
+                };
+                if hoist
+                    && section.nodes.len() == 1
+                    && section.title.is_none()
+                    && section.kind == SectionKind::Generic
+                {
+                    let (lang, dir) = (section.lang.take(), section.dir);
+                    if let Some(node) = section.nodes.pop() {
+                        *self = node.into_raw();
+                        // Keep the language of the removed section.
+                        if lang.is_some() || dir.is_some() {
+                            self.set_language(lang, dir);
+                        }
+                    }
+                }
+            }
+            Self::Text(text) => text.minimize(options),
+            Self::Form(form) => Self::minimize_nodes(&mut form.nodes, options),
+            Self::Field(_) => {}
+            // Code keeps its whitespace.
+            Self::Code(_) => {}
+            Self::Details(details) => {
+                details.summary.minimize(options);
+                Self::minimize_nodes(&mut details.nodes, options);
+            }
+        }
+    }
+
+    /// Minimize child nodes, removing those left empty.
+    fn minimize_nodes(nodes: &mut Vec<Node>, options: &MinimizeOptions) {
+        for node in nodes.iter_mut() {
+            node.minimize_with(options);
+        }
+        nodes.retain(|n| {
+            let empty = options.remove_empty && n.is_empty();
+            let blank = options.drop_blank_text && matches!(&**n, Self::Text(t) if t.is_blank());
+            !empty && !blank && !matches!(&**n, Self::Empty)
+        });
+    }
+}
+
+impl Section {
+    /// Check if the section is an untitled, generic set, which only groups
+    /// its nodes.
+    fn is_plain_set(&self) -> bool {
+        self.ordering == SectionOrdering::Set
+            && self.title.is_none()
+            && self.kind == SectionKind::Generic
+            && self.lang.is_none()
+            && self.dir.is_none()
+    }
+
+    /// Replace nested plain sets with their nodes.
+    fn splice_sets(&mut self) {
+        if !self
+            .nodes
+            .iter()
+            .any(|n| matches!(&**n, RawNode::Section(s) if s.is_plain_set()))
+        {
+            return;
+        }
+        let nodes = std::mem::take(&mut self.nodes);
+        for node in nodes {
+            match node.into_raw() {
+                RawNode::Section(section) if section.is_plain_set() => {
+                    self.nodes.extend(section.nodes);
+                }
+                raw => self.nodes.push(raw.into()),
+            }
+        }
+    }
+}
+
+impl Text {
+    /// Minimize fragments of the text.
+    pub fn minimize(&mut self, options: &MinimizeOptions) {
+        if options.drop_blank_text && !self.is_blank() {
+            let blank =
+                |f: &TextFragment| f.text.trim().is_empty() && f.attributes.annotation.is_none();
+            while self.fragments.last().is_some_and(blank) {
+                self.fragments.pop();
+            }
+            let leading = self.fragments.iter().take_while(|f| blank(f)).count();
+            self.fragments.drain(..leading);
+        }
+        if options.merge_fragments {
+            self.merge_fragments();
+        }
+    }
+
+    /// Merge adjacent fragments with equal attributes.
+    /// Annotated fragments, like ruby, are kept apart.
+    pub fn merge_fragments(&mut self) {
+        let mut merged: Vec<TextFragment> = Vec::with_capacity(self.fragments.len());
+        for frag in self.fragments.drain(..) {
+            match merged.last_mut() {
+                Some(last)
+                    if last.attributes == frag.attributes
+                        && last.attributes.annotation.is_none() =>
+                {
+                    last.text.push_str(&frag.text);
+                }
+                _ => merged.push(frag),
+            }
+        }
+        self.fragments = merged;
+    }
+}
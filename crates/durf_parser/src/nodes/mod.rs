@@ -1,50 +1,86 @@
 use super::*;
 
+mod code;
+mod details;
+mod form;
+mod lang;
 mod media;
+mod minimize;
+mod path;
 mod section;
+mod style;
 mod text;
 
+pub use code::*;
+pub use details::*;
+pub use form::*;
+pub use lang::*;
 #[allow(unused)]
 pub use media::*;
+pub use minimize::*;
+pub use path::*;
 pub use section::*;
+pub use style::*;
 pub use text::*;
 
-/// A boxed RawNode for use in the AST.
+/// A shared RawNode for use in the AST.
+/// Clones share the node and its descendents, and mutable access copies the
+/// node first if it is shared, so edits never affect other clones.
 #[derive(Clone, Debug)]
-pub struct Node(Box<RawNode>);
+pub struct Node(Arc<RawNode>);
 
 impl Node {
     /// Create a new nade from a raw node.
     pub fn new(raw_node: RawNode) -> Self {
         raw_node.into()
     }
+
+    /// Unwrap the raw node, copying it if it is shared.
+    pub fn into_raw(self) -> RawNode {
+        Arc::try_unwrap(self.0).unwrap_or_else(|node| (*node).clone())
+    }
+
+    /// Check if two nodes share the same storage.
+    pub fn ptr_eq(&self, other: &Node) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
 }
 
 impl std::ops::Deref for Node {
     type Target = RawNode;
     fn deref(&self) -> &Self::Target {
-        &*self.0
+        &self.0
     }
 }
 
 impl std::ops::DerefMut for Node {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut *self.0
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || *self.0 == *other.0
     }
 }
 
 impl From<RawNode> for Node {
     fn from(value: RawNode) -> Self {
-        Self(Box::new(value))
+        Self(Arc::new(value))
     }
 }
 
 /// A node in the durf AST.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum RawNode {
     Empty,
     Section(Section),
     Text(Text),
+    Details(Details),
+    Form(Form),
+    Field(Field),
+    Code(Code),
 }
 
 impl From<Section> for RawNode {
@@ -53,6 +89,30 @@ impl From<Section> for RawNode {
     }
 }
 
+impl From<Details> for RawNode {
+    fn from(value: Details) -> Self {
+        RawNode::Details(value)
+    }
+}
+
+impl From<Form> for RawNode {
+    fn from(value: Form) -> Self {
+        RawNode::Form(value)
+    }
+}
+
+impl From<Field> for RawNode {
+    fn from(value: Field) -> Self {
+        RawNode::Field(value)
+    }
+}
+
+impl From<Code> for RawNode {
+    fn from(value: Code) -> Self {
+        RawNode::Code(value)
+    }
+}
+
 impl From<Text> for RawNode {
     fn from(value: Text) -> Self {
         RawNode::Text(value)
@@ -62,36 +122,76 @@ impl From<Text> for RawNode {
 impl RawNode {
     pub(crate) fn from_element_ref(
         ele: &scraper::ElementRef,
-        flags: &mut ParseFlags,
+        flags: &mut ParseContext,
     ) -> Result<Self, Error> {
         Self::from_element_ref_internal(ele, flags)
     }
 
     fn from_element_ref_text(
         ele: &scraper::ElementRef,
-        flags: &mut ParseFlags,
+        flags: &mut ParseContext,
     ) -> Result<Text, Error> {
-        let ele_name = ele.value().name.local.to_ascii_lowercase();
+        if flags.is_exhausted() {
+            return Ok(Text::new_empty());
+        }
+        let semantic = flags.semantic(ele).cloned();
+        let ele_name = match semantic.as_ref().and_then(Semantic::element_name) {
+            Some(name) => name.into(),
+            None => ele.value().name.local.to_ascii_lowercase(),
+        };
 
         // Do not exceed depth.
         tracing::trace!("Depth: {} {ele_name}", flags.remaining_depth);
         if flags.remaining_depth == 0 {
-            return Err(Error::DepthExceeded);
+            let err = flags.depth_exceeded(&ele_name);
+            return match flags.depth_policy {
+                DepthPolicy::Error => Err(err),
+                DepthPolicy::Truncate => Ok(Text::truncated()),
+                DepthPolicy::Flatten => {
+                    let mut text = Text::new();
+                    for block in Self::flatten(ele, flags) {
+                        text.extend(block);
+                    }
+                    Ok(text)
+                }
+            };
         }
 
-        let mut flags = DepthGuard::new(flags);
+        let mut flags = DepthGuard::new(flags, &ele_name);
+        flags.inherit_language(ele);
+
+        Self::parse_text(ele, &ele_name, semantic.as_ref(), &mut flags)
+    }
 
+    /// Parse a text element that has already been entered.
+    fn parse_text(
+        ele: &scraper::ElementRef,
+        ele_name: &str,
+        semantic: Option<&Semantic>,
+        flags: &mut ParseContext,
+    ) -> Result<Text, Error> {
         // Combine children of text element into single text.
         let mut text = Text::new();
 
         // Check special cases.
-        match ele_name.as_ref() {
+        if flags.is_hidden(ele) || semantic == Some(&Semantic::Hidden) {
+            flags.report.skip_element(ele_name);
+            return Ok(Text::new_empty());
+        }
+        match ele_name {
             "br" | "hr" => {
-                return Ok(Text::from_fragment("\n"));
+                text.append(flags.fragment("\n"));
+                return Ok(text);
             }
             "rp" => {
                 return Ok(Text::new_empty());
             }
+            "noscript" => {
+                if let Some(fragment) = Self::noscript_fragment(ele) {
+                    flags.record_fallback(FallbackKind::NoscriptMarkup);
+                    return Self::from_element_ref_text(&fragment.root_element(), flags);
+                }
+            }
             _ => {}
         }
 
@@ -102,7 +202,7 @@ impl RawNode {
 
             // Parse child elements.
             if let Some(sub_ele_ref) = scraper::ElementRef::wrap(node_ref) {
-                if let Ok(sub_text) = Self::from_element_ref_text(&sub_ele_ref, flags.deref_mut()) {
+                if let Ok(sub_text) = Self::from_element_ref_text(&sub_ele_ref, flags) {
                     text.extend(sub_text);
                 }
             }
@@ -112,12 +212,15 @@ impl RawNode {
                 let mut _minimized_text: String = node_text.to_string();
                 _minimized_text = _minimized_text.replace("\n", "");
                 // TODO: Better string minimization.
-                text.append(TextFragment::from(node_text.as_ref()));
+                let node_text = flags.take_text(node_text);
+                if !node_text.is_empty() {
+                    text.append(flags.fragment(node_text));
+                }
             }
         }
 
         // For certain elements, we have special handling:
-        match ele_name.as_ref() {
+        match ele_name {
             // Ruby will combine and wrap children:
             "ruby" => {
                 text.combine_fragments();
@@ -135,11 +238,91 @@ impl RawNode {
             _ => {}
         }
 
+        Self::style_text(ele, ele_name, semantic, &mut text, flags);
+        Ok(text)
+    }
+
+    /// Parse a text element containing form fields into a section, with the
+    /// text around the fields kept in order.
+    fn parse_text_fields(
+        ele: &scraper::ElementRef,
+        ele_name: &str,
+        semantic: Option<&Semantic>,
+        flags: &mut ParseContext,
+    ) -> Self {
+        let mut section = Section::new_set();
+        let mut text = Text::new();
+        let push_text = |section: &mut Section, text: &mut Text, flags: &mut ParseContext| {
+            let mut text = std::mem::take(text);
+            if !text.is_blank() {
+                Self::style_text(ele, ele_name, semantic, &mut text, flags);
+                section.nodes.push(Node::new(text.into()));
+            }
+        };
+        for node_ref in ele.children() {
+            match scraper::ElementRef::wrap(node_ref) {
+                Some(child)
+                    if child.value().name.local.as_ref() == "label" || Self::has_field(&child) =>
+                {
+                    push_text(&mut section, &mut text, flags);
+                    match RawNode::from_element_ref_internal(&child, flags) {
+                        Ok(parsed_child) => section.nodes.push(parsed_child.into()),
+                        Err(e) => {
+                            tracing::debug!("Failed to parse child: {e:?}");
+                        }
+                    }
+                }
+                Some(child) => {
+                    if let Ok(sub_text) = Self::from_element_ref_text(&child, flags) {
+                        text.extend(sub_text);
+                    }
+                }
+                None => {
+                    if let Some(node_text) = node_ref.value().as_text() {
+                        let node_text = flags.take_text(node_text);
+                        if !node_text.is_empty() {
+                            text.append(flags.fragment(node_text));
+                        }
+                    }
+                }
+            }
+        }
+        push_text(&mut section, &mut text, flags);
+        section.into()
+    }
+
+    /// Set the attributes of the fragments of a text element.
+    fn style_text(
+        ele: &scraper::ElementRef,
+        ele_name: &str,
+        semantic: Option<&Semantic>,
+        text: &mut Text,
+        flags: &mut ParseContext,
+    ) {
         // Modify child fragments according to element.
+        // Links are kept when a semantic replaces the element.
+        let link = match ele.value().name.local.as_ref() {
+            "p" | "a" | "span" => flags.attr(ele, "href"),
+            _ => None,
+        };
+        let mut plain = false;
+
+        // Apply inline and class styles, which take priority over the
+        // element's own bold and italic.
+        let style = TextStyle::from_element(ele, flags);
+        if !style.is_empty() {
+            for frag in &mut text.fragments {
+                style.apply(&mut frag.attributes);
+            }
+        }
+
         for frag in &mut text.fragments {
-            match ele_name.as_ref() {
+            if let Some(link) = link {
+                frag.attributes.link = Some(link.into());
+            }
+            match ele_name {
                 "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
-                    frag.attributes.heading = match ele_name.as_ref() {
+                    frag.attributes.heading = match ele_name {
                         "h1" => Some(1),
                         "h2" => Some(2),
                         "h3" => Some(3),
@@ -152,60 +335,135 @@ impl RawNode {
                         }
                     };
                 }
-                "p" | "a" | "span" => {
-                    if let Some(link) = ele.attr("href") {
-                        frag.attributes.link = Some(link.into());
-                    }
-                }
+                "p" | "a" | "span" => {}
                 "strong" | "b" => {
-                    frag.attributes.bold = true;
+                    frag.attributes.bold |= frag.attributes.weight.is_none();
                 }
                 "i" | "u" | "em" => {
-                    frag.attributes.italic = true;
+                    frag.attributes.italic |= frag.attributes.italic_style.is_none();
                 }
                 "blockquote" | "q" | "pre" | "code" => {
                     frag.attributes.preformatted = true;
                 }
+                // Direction is inherited, with the language.
+                "bdi" => {}
+                "bdo" => {
+                    frag.attributes.bidi_override = true;
+                }
                 _ => {
-                    tracing::debug!("Unsupported text element: {}", ele_name.as_ref());
+                    tracing::debug!("Unsupported text element: {}", ele_name);
+                    plain = true;
                 }
             }
         }
+        if plain {
+            flags.record_fallback(FallbackKind::PlainText);
+        }
+        if let Some(Semantic::Highlight(name)) = semantic {
+            text.highlight(name);
+        }
+    }
 
-        Ok(text)
+    /// Parse bare text inside a section, like text directly in the body.
+    /// Blank text is skipped.
+    pub(crate) fn from_text_node(text: &str, flags: &mut ParseContext) -> Option<Self> {
+        if text.trim().is_empty() || !flags.parsing || flags.is_exhausted() {
+            return None;
+        }
+        let text = flags.take_text(text);
+        if text.trim().is_empty() {
+            return None;
+        }
+        flags.report.nodes += 1;
+        let mut node = Text::new();
+        node.append(flags.fragment(text));
+        Some(node.into())
     }
 
     fn from_element_ref_internal(
         elem: &scraper::ElementRef,
-        flags: &mut ParseFlags,
+        flags: &mut ParseContext,
     ) -> Result<Self, Error> {
+        if flags.is_exhausted() {
+            return Ok(Self::Empty);
+        }
+
         // Do not exceed depth.
+        let name = elem.value().name.local.to_ascii_lowercase();
         if flags.remaining_depth == 0 {
-            return Err(Error::DepthExceeded);
+            if flags.should_skip(elem) || (!flags.parsing && !flags.should_parse(elem)) {
+                return Ok(Self::Empty);
+            }
+            let err = flags.depth_exceeded(&name);
+            return match flags.depth_policy {
+                DepthPolicy::Error => Err(err),
+                DepthPolicy::Truncate => Ok(Text::truncated().into()),
+                DepthPolicy::Flatten => {
+                    let mut section = Section::new_set();
+                    for block in Self::flatten(elem, flags) {
+                        flags.report.nodes += 1;
+                        section.nodes.push(Node::new(block.into()));
+                    }
+                    Ok(section.into())
+                }
+            };
         }
 
-        let mut flags = DepthGuard::new(flags);
+        let mut flags = DepthGuard::new(flags, &name);
+        let language = flags.inherit_language(elem);
 
-        let mut toggled_parsing = false;
-        if !flags.parsing {
-            if flags.should_parse(elem) {
-                toggled_parsing = true;
-                flags.parsing = true;
-            }
-        }
-        if flags.should_skip(elem) {
+        let semantic = flags.semantic(elem).cloned();
+        if flags.should_skip(elem) || semantic == Some(Semantic::Hidden) {
+            flags.report.skip_element(&name);
             return Ok(Self::Empty);
         }
+        flags.report.nodes += 1;
+        let mut toggled_parsing = false;
+        if !flags.parsing && flags.should_parse(elem) {
+            toggled_parsing = true;
+            flags.parsing = true;
+        }
 
-        // Match on element name.
-        let ele_name = elem.value().name.local.to_ascii_lowercase();
+        // Match on element name, or the element the semantic replaces it with.
+        let ele_name = match semantic.as_ref().and_then(Semantic::element_name) {
+            Some(name) => name.into(),
+            None => elem.value().name.local.to_ascii_lowercase(),
+        };
         tracing::trace!("Tag {ele_name}");
-        let parsed: Result<Self, Error> = match ele_name.as_ref() {
+        let mut parsed: Result<Self, Error> = match ele_name.as_ref() {
             // TODO: Parse head as meta!
             "html" | "header" | "footer" | "body" | "div" | "section" | "article" | "main"
-            | "nav" => {
+            | "nav" | "aside" => {
+                let kind = elem
+                    .attr("role")
+                    .and_then(SectionKind::from_role)
+                    .unwrap_or_else(|| SectionKind::from_element(ele_name.as_ref()));
+                let mut section = Section::new_set().with_kind(kind);
+                for child in elem.children() {
+                    let Some(child) = scraper::ElementRef::wrap(child) else {
+                        let text = child.value().as_text().map(|text| &**text);
+                        if let Some(node) = text.and_then(|t| Self::from_text_node(t, &mut flags)) {
+                            section.nodes.push(node.into());
+                        }
+                        continue;
+                    };
+                    match RawNode::from_element_ref_internal(&child, flags.deref_mut()) {
+                        Ok(parsed_child) => section.nodes.push(parsed_child.into()),
+                        Err(e) => {
+                            tracing::debug!("Failed to parse child: {e:?}");
+                        }
+                    }
+                }
+                Ok(section.into())
+            }
+            "noscript" => {
                 let mut section = Section::new_set();
-                for child in elem.child_elements() {
+                let fragment = Self::noscript_fragment(elem);
+                if fragment.is_some() {
+                    flags.record_fallback(FallbackKind::NoscriptMarkup);
+                }
+                let root = fragment.as_ref().map(|f| f.root_element());
+                for child in root.as_ref().unwrap_or(elem).child_elements() {
                     match RawNode::from_element_ref_internal(&child, flags.deref_mut()) {
                         Ok(parsed_child) => section.nodes.push(parsed_child.into()),
                         Err(e) => {
@@ -215,6 +473,103 @@ impl RawNode {
                 }
                 Ok(section.into())
             }
+            "details" => {
+                let mut summary: Option<Text> = None;
+                let mut nodes = Vec::new();
+                for child in elem.child_elements() {
+                    if summary.is_none() && child.value().name.local.as_ref() == "summary" {
+                        match Self::from_element_ref_text(&child, flags.deref_mut()) {
+                            Ok(text) => summary = Some(text),
+                            Err(e) => {
+                                tracing::debug!("Failed to parse summary: {e:?}");
+                            }
+                        }
+                        continue;
+                    }
+                    match RawNode::from_element_ref_internal(&child, flags.deref_mut()) {
+                        Ok(parsed_child) => nodes.push(parsed_child.into()),
+                        Err(e) => {
+                            tracing::debug!("Failed to parse child: {e:?}");
+                        }
+                    }
+                }
+                let summary = summary.unwrap_or_else(|| {
+                    flags.record_fallback(FallbackKind::DefaultSummary);
+                    Text::from_fragment("Details")
+                });
+                let mut details = Details::new(summary).with_open(elem.attr("open").is_some());
+                details.nodes = nodes;
+                Ok(details.into())
+            }
+            "form" => {
+                let mut form = Form::new();
+                form.action = flags.attr(elem, "action").map(String::from);
+                form.method = elem
+                    .attr("method")
+                    .map(FormMethod::from_attr)
+                    .unwrap_or_default();
+                if let Some(enctype) = flags.attr(elem, "enctype") {
+                    form.enctype = enctype.into();
+                }
+                for child in elem.child_elements() {
+                    match RawNode::from_element_ref_internal(&child, flags.deref_mut()) {
+                        Ok(parsed_child) => form.nodes.push(parsed_child.into()),
+                        Err(e) => {
+                            tracing::debug!("Failed to parse child: {e:?}");
+                        }
+                    }
+                }
+                Ok(form.into())
+            }
+            "input" | "select" | "textarea" | "button" | "label" if !flags.parsing => {
+                Ok(RawNode::Empty)
+            }
+            "input" | "select" | "textarea" | "button" => {
+                Ok(Self::parse_field(elem, None, &mut flags).into())
+            }
+            // Labelled fields show their own label, taking its text once.
+            "label" if !Self::has_field(elem) && Self::labels_element(elem, &mut flags) => {
+                Ok(RawNode::Empty)
+            }
+            "label" => {
+                let label = Self::parse_text(elem, &ele_name, semantic.as_ref(), &mut flags)?;
+                let label_text: String = label.collect().trim().into();
+                let mut fields: Vec<Node> = elem
+                    .descendent_elements()
+                    .filter(|e| FormLabels::FIELDS.contains(&e.value().name.local.as_ref()))
+                    .map(|e| {
+                        let field = Self::parse_field(&e, Some(label_text.clone()), &mut flags);
+                        Node::new(field.into())
+                    })
+                    .collect();
+                match fields.len() {
+                    0 => Ok(label.into()),
+                    1 => Ok(fields.remove(0).into_raw()),
+                    _ => {
+                        let mut section = Section::new_set();
+                        section.nodes = fields;
+                        Ok(section.into())
+                    }
+                }
+            }
+            "pre" if !flags.parsing => Ok(RawNode::Empty),
+            "pre" => {
+                // Language is usually set on an inner code element.
+                let language = Code::language_from_element(elem, &mut flags).or_else(|| {
+                    elem.child_elements()
+                        .find(|e| e.value().name.local.as_ref() == "code")
+                        .and_then(|e| Code::language_from_element(&e, &mut flags))
+                });
+                let text = Code::text_from_element(elem, &flags);
+                let mut code = Code::new(flags.take_text(&text), language);
+                code.highlight();
+                Ok(code.into())
+            }
+            "dl" => {
+                let mut section = Section::new_definition();
+                Self::parse_definitions(elem, &mut section, flags.deref_mut());
+                Ok(section.into())
+            }
             "menu" | "ul" => {
                 let mut section = Section::new_list();
                 for child in elem.child_elements() {
@@ -228,30 +583,377 @@ impl RawNode {
                 Ok(section.into())
             }
             "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p" | "a" | "span" | "strong" | "em"
-            | "i" | "s" | "u" | "blockquote" | "q" | "hr" | "br" | "pre" | "code" => {
-                if flags.parsing {
-                    let res = match Self::from_element_ref_text(elem, flags.deref_mut()) {
+            | "i" | "s" | "u" | "blockquote" | "q" | "hr" | "br" | "code" | "summary" | "bdi"
+            | "bdo" | "rt" => {
+                if flags.parsing && Self::has_field(elem) {
+                    Ok(Self::parse_text_fields(
+                        elem,
+                        &ele_name,
+                        semantic.as_ref(),
+                        &mut flags,
+                    ))
+                } else if flags.parsing {
+                    match Self::parse_text(elem, &ele_name, semantic.as_ref(), &mut flags) {
                         Ok(t) => Ok(t.into()),
                         Err(e) => Err(e),
-                    };
-                    res
+                    }
                 } else {
                     Ok(RawNode::Empty)
                 }
             }
             _ => {
                 tracing::debug!("Unsupported element: {}", ele_name,);
+                // The head only holds metadata, which is never rendered.
+                if &*ele_name != "head" {
+                    flags.report.drop_element(&ele_name);
+                }
 
-                Err(Error::Todo)
+                Err(Error::UnsupportedElement {
+                    element: ele_name.to_string(),
+                    path: flags.path.join(" > "),
+                })
             }
         };
 
         if toggled_parsing {
             flags.parsing = false;
         }
+        if let (Ok(node), Some(Semantic::Highlight(name))) = (&mut parsed, &semantic) {
+            node.for_each_text_mut(&mut |text| text.highlight(name));
+        }
+        if let Ok(node) = &mut parsed {
+            flags.set_language(node);
+            if let Self::Section(section) = node {
+                (section.lang, section.dir) = language;
+            }
+        }
         parsed
     }
 
+    /// Flatten an element into blocks of text, without recursion, other than
+    /// into the markup of `noscript` elements.
+    /// Inline formatting and ruby annotations are kept, while nesting of
+    /// blocks is not.
+    fn flatten(elem: &scraper::ElementRef, flags: &mut ParseContext) -> Vec<Text> {
+        const BLOCKS: &[&str] = &[
+            "html",
+            "body",
+            "div",
+            "section",
+            "article",
+            "main",
+            "nav",
+            "aside",
+            "header",
+            "footer",
+            "p",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+            "ul",
+            "ol",
+            "menu",
+            "li",
+            "dl",
+            "dt",
+            "dd",
+            "blockquote",
+            "pre",
+            "table",
+            "tr",
+            "td",
+            "th",
+            "form",
+            "details",
+            "summary",
+            "figure",
+            "figcaption",
+            "fieldset",
+        ];
+
+        let mut blocks = Vec::new();
+        let mut text = Text::new();
+        let mut current_block = None;
+        // The `rt` element of the last annotation fragment.
+        let mut current_annotation = None;
+        for node in elem.descendants() {
+            // Ancestors within the flattened element, innermost first.
+            let mut ancestors = Vec::new();
+            for ancestor in node.ancestors().filter_map(scraper::ElementRef::wrap) {
+                ancestors.push(ancestor);
+                if ancestor == *elem {
+                    break;
+                }
+            }
+            let noscript = |e: &scraper::ElementRef| {
+                e.value().name.local.as_ref() == "noscript" && e.child_elements().next().is_none()
+            };
+
+            // Noscript markup is parsed as a fragment, and flattened on its own.
+            if let Some(element) = scraper::ElementRef::wrap(node).filter(noscript) {
+                let skipped = std::iter::once(&element)
+                    .chain(&ancestors)
+                    .any(|a| flags.should_skip(a) || flags.semantic(a) == Some(&Semantic::Hidden));
+                if let Some(fragment) = Self::noscript_fragment(&element).filter(|_| !skipped) {
+                    flags.record_fallback(FallbackKind::NoscriptMarkup);
+                    let block_text = std::mem::take(&mut text);
+                    if !block_text.is_blank() {
+                        blocks.push(block_text);
+                    }
+                    blocks.extend(Self::flatten(&fragment.root_element(), flags));
+                    current_block = None;
+                    let max_blocks = flags.limits.max_nodes.saturating_sub(flags.report.nodes);
+                    if blocks.len() > max_blocks {
+                        blocks.truncate(max_blocks);
+                        flags.hit_limit(Limit::Nodes);
+                        break;
+                    }
+                }
+                continue;
+            }
+            let Some(content) = node.value().as_text() else {
+                continue;
+            };
+            if ancestors.iter().any(|a| {
+                flags.should_skip(a)
+                    || flags.semantic(a) == Some(&Semantic::Hidden)
+                    || noscript(a)
+                    || a.value().name.local.as_ref() == "rp"
+            }) {
+                continue;
+            }
+
+            // Start a new block when the nearest block ancestor changes.
+            let block = ancestors
+                .iter()
+                .find(|a| BLOCKS.contains(&a.value().name.local.as_ref()))
+                .unwrap_or(elem)
+                .id();
+            if current_block != Some(block) {
+                let block_text = std::mem::take(&mut text);
+                if !block_text.is_blank() {
+                    blocks.push(block_text);
+                }
+                current_block = Some(block);
+                // Each block becomes a node.
+                if flags.report.nodes + blocks.len() >= flags.limits.max_nodes {
+                    flags.hit_limit(Limit::Nodes);
+                    break;
+                }
+                if flags.is_exhausted() {
+                    break;
+                }
+            }
+
+            let content = flags.take_text(content);
+            if content.is_empty() {
+                continue;
+            }
+
+            // Ruby text is an annotation of the text before it, like `rt`.
+            let annotation = ancestors.iter().find(|a| {
+                a.value().name.local.as_ref() == "rt"
+                    || flags.semantic(a) == Some(&Semantic::Annotation)
+            });
+            if let Some(annotation) = annotation {
+                let id = Some(annotation.id());
+                match text.fragments.last_mut() {
+                    Some(last) if current_annotation == id => {
+                        last.attributes
+                            .annotation
+                            .get_or_insert_with(String::new)
+                            .push_str(content);
+                    }
+                    _ => {
+                        let mut frag = flags.fragment("");
+                        frag.attributes.annotation = Some(content.into());
+                        text.append(frag);
+                        current_annotation = id;
+                    }
+                }
+                continue;
+            }
+            current_annotation = None;
+
+            let mut frag = flags.fragment(content);
+            for ancestor in &ancestors {
+                let attributes = &mut frag.attributes;
+                match ancestor.value().name.local.as_ref() {
+                    "strong" | "b" => attributes.bold = true,
+                    "i" | "u" | "em" => attributes.italic = true,
+                    "code" | "pre" => attributes.preformatted = true,
+                    "a" if attributes.link.is_none() => {
+                        attributes.link = flags.attr(ancestor, "href").map(String::from);
+                    }
+                    name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6")
+                        if attributes.heading.is_none() =>
+                    {
+                        attributes.heading = name[1..].parse().ok();
+                    }
+                    _ => {}
+                }
+            }
+            text.append(frag);
+        }
+        if !text.is_blank() {
+            blocks.push(text);
+        }
+        blocks
+    }
+
+    /// Parse the content of a `noscript` element.
+    /// With scripting enabled, the document parser keeps the content as raw
+    /// text, so it is parsed again as a fragment.
+    fn noscript_fragment(elem: &scraper::ElementRef) -> Option<scraper::Html> {
+        if elem.child_elements().next().is_some() {
+            return None;
+        }
+        let markup: String = elem.text().collect();
+        Some(scraper::Html::parse_fragment(&markup))
+    }
+
+    /// Parse a form field element.
+    fn parse_field(
+        elem: &scraper::ElementRef,
+        label: Option<String>,
+        flags: &mut ParseContext,
+    ) -> Field {
+        let ele_name = elem.value().name.local.to_ascii_lowercase();
+        let kind = match ele_name.as_ref() {
+            "select" => {
+                let mut options: Vec<SelectOption> = elem
+                    .descendent_elements()
+                    .filter(|e| e.value().name.local.as_ref() == "option")
+                    .map(|e| {
+                        let label = flags.element_text(&e);
+                        SelectOption {
+                            value: flags
+                                .attr(&e, "value")
+                                .map(String::from)
+                                .unwrap_or(label.clone()),
+                            label,
+                            selected: e.attr("selected").is_some(),
+                        }
+                    })
+                    .collect();
+                // Without a selection, the first option is selected.
+                if elem.attr("multiple").is_none() && !options.iter().any(|o| o.selected) {
+                    if let Some(first) = options.first_mut() {
+                        first.selected = true;
+                    }
+                }
+                FieldKind::Select(options)
+            }
+            "textarea" => FieldKind::TextArea,
+            "button" => match elem.attr("type") {
+                Some(t) if !t.eq_ignore_ascii_case("submit") => FieldKind::Button,
+                _ => FieldKind::Submit,
+            },
+            _ => FieldKind::from_input_type(elem.attr("type").unwrap_or("text")),
+        };
+
+        let mut field = Field::new(kind);
+        field.name = flags.attr(elem, "name").map(String::from);
+        field.placeholder = flags.attr(elem, "placeholder").map(String::from);
+        field.checked = elem.attr("checked").is_some();
+        field.disabled = elem.attr("disabled").is_some();
+        field.value = match (&field.kind, ele_name.as_ref()) {
+            (_, "textarea") => flags.take_text(&elem.text().collect::<String>()).into(),
+            (FieldKind::Checkbox | FieldKind::Radio, _) => {
+                flags.attr(elem, "value").unwrap_or("on").into()
+            }
+            _ => flags.attr(elem, "value").unwrap_or_default().into(),
+        };
+        field.label = match ele_name.as_ref() {
+            "button" => Some(flags.element_text(elem)),
+            _ => label.or_else(|| {
+                let id = elem.attr("id")?;
+                let label = Self::form_labels(elem, flags).label(id)?;
+                let label = elem.tree().get(label).and_then(scraper::ElementRef::wrap)?;
+                Some(flags.element_text(&label))
+            }),
+        };
+        field
+    }
+
+    /// Labels of the form fields in the document of an element.
+    fn form_labels<'a>(elem: &scraper::ElementRef, flags: &'a mut ParseContext) -> &'a FormLabels {
+        flags
+            .labels
+            .get_or_insert_with(|| FormLabels::from_element(elem))
+    }
+
+    /// Check if a `label` element references a form field by id.
+    fn labels_element(label: &scraper::ElementRef, flags: &mut ParseContext) -> bool {
+        let Some(id) = label.attr("for") else {
+            return false;
+        };
+        Self::form_labels(label, flags).is_field(id)
+    }
+
+    /// Check if an element is or contains a form field.
+    fn has_field(elem: &scraper::ElementRef) -> bool {
+        elem.descendent_elements()
+            .any(|e| FormLabels::FIELDS.contains(&e.value().name.local.as_ref()))
+    }
+
+    /// Parse terms and descriptions of a definition list into groups.
+    fn parse_definitions(elem: &scraper::ElementRef, list: &mut Section, flags: &mut ParseContext) {
+        for child in elem.child_elements() {
+            let child_name = child.value().name.local.to_ascii_lowercase();
+            match child_name.as_ref() {
+                "dt" | "dd" => {
+                    let text = match Self::from_element_ref_text(&child, flags) {
+                        Ok(text) => text,
+                        Err(e) => {
+                            tracing::debug!("Failed to parse child: {e:?}");
+                            continue;
+                        }
+                    };
+
+                    // Start a new group for a term following descriptions.
+                    let needs_group = match list.nodes.last() {
+                        Some(last) => match &**last {
+                            RawNode::Section(group) => {
+                                child_name.as_ref() == "dt" && !group.nodes.is_empty()
+                            }
+                            _ => true,
+                        },
+                        None => true,
+                    };
+                    if needs_group {
+                        // Groups always have a title so they are never collapsed.
+                        let group = Section::new_set().with_title(Text::new_empty());
+                        list.nodes.push(Node::new(group.into()));
+                    }
+                    let Some(RawNode::Section(group)) = list.nodes.last_mut().map(|n| &mut **n)
+                    else {
+                        continue;
+                    };
+
+                    if child_name.as_ref() == "dt" {
+                        let title = group.title.get_or_insert_with(Text::new);
+                        if !title.is_blank() {
+                            title.append(TextFragment::from(", "));
+                        }
+                        title.extend(text);
+                    } else {
+                        group.nodes.push(Node::new(text.into()));
+                    }
+                }
+                // Groups may be wrapped with a div.
+                "div" => Self::parse_definitions(&child, list, flags),
+                _ => {
+                    tracing::debug!("Unsupported definition element: {}", child_name);
+                    flags.report.drop_element(&child_name);
+                }
+            }
+        }
+    }
+
     pub fn export_string(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
         for _ in 0..depth {
             write!(f, " ")?;
@@ -259,58 +961,190 @@ impl RawNode {
         match self {
             Self::Empty => {}
             Self::Section(section) => {
-                write!(f, "Section\n").ok();
+                if section.ordering == SectionOrdering::Definition {
+                    writeln!(f, "Definitions")?;
+                    for (term, descriptions) in section.definitions() {
+                        for _ in 0..depth + 1 {
+                            write!(f, " ")?;
+                        }
+                        match term {
+                            Some(term) => writeln!(f, "Term: **{}**", term.collect())?,
+                            None => writeln!(f, "Term:")?,
+                        };
+                        for description in descriptions {
+                            description.export_string(f, depth + 2)?;
+                        }
+                    }
+                    return Ok(());
+                }
+                write!(f, "Section")?;
+                if section.kind != SectionKind::Generic {
+                    write!(f, " ({:?})", section.kind)?;
+                }
+                if let Some(lang) = &section.lang {
+                    write!(f, " [lang={lang}]")?;
+                }
+                if let Some(dir) = &section.dir {
+                    write!(f, " [dir={dir}]")?;
+                }
+                match &section.title {
+                    Some(title) => writeln!(f, ": {}", title.to_markdown())?,
+                    None => writeln!(f)?,
+                };
                 for child in &section.nodes {
                     child.export_string(f, depth + 1)?;
                 }
             }
             Self::Text(text) => {
-                write!(f, "Text: {}\n", text.to_markdown()).ok();
+                writeln!(f, "Text: {}", text.to_markdown()).ok();
+            }
+            Self::Code(code) => {
+                match &code.language {
+                    Some(language) => writeln!(f, "Code ({language})")?,
+                    None => writeln!(f, "Code")?,
+                };
+                for line in code.text.collect().lines() {
+                    for _ in 0..depth + 1 {
+                        write!(f, " ")?;
+                    }
+                    writeln!(f, "| {line}")?;
+                }
+            }
+            Self::Form(form) => {
+                writeln!(
+                    f,
+                    "Form ({:?} {}): {}",
+                    form.method,
+                    form.action.as_deref().unwrap_or(""),
+                    form.enctype
+                )?;
+                for child in &form.nodes {
+                    child.export_string(f, depth + 1)?;
+                }
+            }
+            Self::Field(field) => {
+                write!(f, "Field: {:?}", field.kind)?;
+                if let Some(name) = &field.name {
+                    write!(f, " {name}={:?}", field.value)?;
+                }
+                if let Some(label) = &field.label {
+                    write!(f, " ({label})")?;
+                }
+                writeln!(f)?;
+            }
+            Self::Details(details) => {
+                match details.open {
+                    true => writeln!(f, "Details (open): {}", details.summary.to_markdown())?,
+                    false => writeln!(f, "Details: {}", details.summary.to_markdown())?,
+                };
+                for child in &details.nodes {
+                    child.export_string(f, depth + 1)?;
+                }
             }
         }
 
         Ok(())
     }
 
-    pub(crate) fn minimize(&mut self) {
+    /// Check if the node would not show anything.
+    /// Text is never considered empty.
+    pub fn is_empty(&self) -> bool {
         match self {
-            Self::Empty => {}
+            Self::Empty => true,
+            Self::Section(s) => s.is_empty(),
+            Self::Text(_) => false,
+            Self::Details(d) => d.is_empty(),
+            Self::Form(f) => f.is_empty(),
+            Self::Field(_) => false,
+            Self::Code(c) => c.text.is_blank(),
+        }
+    }
+
+    /// Child nodes of containers.
+    pub fn children(&self) -> &[Node] {
+        match self {
+            Self::Section(section) => &section.nodes,
+            Self::Details(details) => &details.nodes,
+            Self::Form(form) => &form.nodes,
+            _ => &[],
+        }
+    }
+
+    /// Mutable child nodes of containers.
+    pub fn children_mut(&mut self) -> &mut [Node] {
+        match self {
+            Self::Section(section) => &mut section.nodes,
+            Self::Details(details) => &mut details.nodes,
+            Self::Form(form) => &mut form.nodes,
+            _ => &mut [],
+        }
+    }
+
+    /// Visit all text of the node and its descendents, including titles.
+    pub fn for_each_text_mut(&mut self, f: &mut impl FnMut(&mut Text)) {
+        match self {
+            Self::Text(text) => f(text),
             Self::Section(section) => {
-                if section.nodes.len() == 0 {
-                    return;
+                if let Some(title) = &mut section.title {
+                    f(title);
                 }
+            }
+            Self::Details(details) => f(&mut details.summary),
+            Self::Code(code) => f(&mut code.text),
+            Self::Empty | Self::Form(_) | Self::Field(_) => {}
+        }
+        for node in self.children_mut() {
+            node.for_each_text_mut(f);
+        }
+    }
 
-                // Remove empty sections.
-                section.nodes.retain(|n| match &**n {
-                    RawNode::Empty => false,
-                    RawNode::Section(s) => !s.is_empty(),
-                    RawNode::Text(_) => true,
-                });
+    /// Set the language and direction of the node and its descendents,
+    /// where they do not have their own.
+    pub fn set_language(&mut self, lang: Option<String>, dir: Option<Direction>) {
+        if let Self::Section(section) = self {
+            if section.lang.is_none() {
+                section.lang = lang.clone();
+            }
+            section.dir = section.dir.or(dir);
+        }
+        let lang = lang.map(Arc::from);
+        self.for_each_text_mut(&mut |text| text.set_language(lang.as_ref(), dir));
+    }
 
-                // Minimize nodes.
-                for node in &mut section.nodes {
-                    node.minimize();
-                }
+    /// Number of nodes, including this node and its descendents.
+    pub fn node_count(&self) -> usize {
+        1 + self
+            .children()
+            .iter()
+            .map(|n| n.node_count())
+            .sum::<usize>()
+    }
 
-                // Remove empty sections.
-                section.nodes.retain(|n| match &**n {
-                    RawNode::Empty => false,
-                    RawNode::Section(s) => !s.is_empty(),
-                    RawNode::Text(_) => true,
-                });
+    /// Heading level of the node, if it is a heading.
+    pub fn heading_level(&self) -> Option<u8> {
+        match self {
+            Self::Text(text) => text.heading_level(),
+            _ => None,
+        }
+    }
 
-                // Collapse subsection.
-                if section.nodes.len() == 1 && section.ordering == SectionOrdering::Set {
-                    if let Some(mut node) = section.nodes.pop() {
-                        // self = &mut *node;
-                        // self = &mut *node;
-                        std::mem::swap(self, &mut *node);
-                    }
-                }
+    /// Find the first section of a kind, depth-first.
+    pub fn find_section(&self, kind: SectionKind) -> Option<&Section> {
+        if let Self::Section(section) = self {
+            if section.kind == kind {
+                return Some(section);
             }
-            Self::Text(text) => {
-                text.clean();
-                // text.text = text.text.trim().into();
+        }
+        self.children().iter().find_map(|n| n.find_section(kind))
+    }
+
+    pub(crate) fn sectionize(&mut self) {
+        match self {
+            Self::Section(section) => section.sectionize(),
+            other => {
+                for node in other.children_mut() {
+                    node.sectionize();
+                }
             }
         }
     }
@@ -0,0 +1,49 @@
+use super::*;
+
+/// Path to a node, as the index of each child from the root.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodePath(pub Vec<usize>);
+
+impl NodePath {
+    pub fn root() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Path to a child of the node.
+    pub fn child(&self, index: usize) -> Self {
+        let mut path = self.0.clone();
+        path.push(index);
+        Self(path)
+    }
+
+    /// Path to the parent of the node, unless it is the root.
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.0.split_last()?;
+        Some(Self(parent.to_vec()))
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for NodePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "/");
+        }
+        for index in &self.0 {
+            write!(f, "/{index}")?;
+        }
+        Ok(())
+    }
+}
+
+impl RawNode {
+    /// Get a descendent by its path from the node.
+    pub fn node_at(&self, path: &NodePath) -> Option<&RawNode> {
+        path.0.iter().try_fold(self, |node, index| {
+            node.children().get(*index).map(|n| &**n)
+        })
+    }
+}
@@ -1,37 +1,85 @@
 use super::*;
 
 /// A section of nodes.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Section {
+    /// Optional title, usually a heading.
+    pub title: Option<Text>,
     /// Nodes in the section.
     pub nodes: Vec<Node>,
     /// Ordering for the nodes.
     pub ordering: SectionOrdering,
+    /// Semantic role of the section.
+    pub kind: SectionKind,
+    /// Language tag, like `ja` or `en-US`.
+    pub lang: Option<String>,
+    /// Text direction.
+    pub dir: Option<Direction>,
 }
 
 impl Section {
     pub fn new_set() -> Self {
         Self {
+            title: None,
             nodes: Vec::new(),
             ordering: SectionOrdering::Set,
+            kind: SectionKind::Generic,
+            lang: None,
+            dir: None,
         }
     }
 
     pub fn new_list() -> Self {
         Self {
+            title: None,
             nodes: Vec::new(),
             ordering: SectionOrdering::List,
+            kind: SectionKind::Generic,
+            lang: None,
+            dir: None,
+        }
+    }
+
+    /// A definition list, where each node is a section titled by its terms
+    /// and containing its descriptions.
+    pub fn new_definition() -> Self {
+        Self {
+            title: None,
+            nodes: Vec::new(),
+            ordering: SectionOrdering::Definition,
+            kind: SectionKind::Generic,
+            lang: None,
+            dir: None,
         }
     }
 
     pub fn new_enumeration() -> Self {
         Self {
+            title: None,
             nodes: Vec::new(),
             ordering: SectionOrdering::Enumeration,
+            kind: SectionKind::Generic,
+            lang: None,
+            dir: None,
         }
     }
 
+    /// Set the semantic kind of the section.
+    pub fn with_kind(mut self, kind: SectionKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Set the title of the section.
+    pub fn with_title(mut self, title: Text) -> Self {
+        self.title = Some(title);
+        self
+    }
+
     pub fn is_empty(&self) -> bool {
+        if self.title.as_ref().is_some_and(|t| !t.is_blank()) {
+            return false;
+        }
         for child in &self.nodes {
             match &**child {
                 RawNode::Section(s) => {
@@ -53,6 +101,76 @@ impl Section {
     pub fn ordering(&self) -> &SectionOrdering {
         &self.ordering
     }
+
+    pub fn title(&self) -> Option<&Text> {
+        self.title.as_ref()
+    }
+
+    pub fn kind(&self) -> &SectionKind {
+        &self.kind
+    }
+
+    /// Iterate terms and descriptions of a definition list.
+    pub fn definitions(&self) -> impl Iterator<Item = (Option<&Text>, &[Node])> {
+        self.nodes.iter().filter_map(|n| match &**n {
+            RawNode::Section(group) => Some((
+                group.title.as_ref().filter(|t| !t.is_blank()),
+                group.nodes.as_slice(),
+            )),
+            _ => None,
+        })
+    }
+
+    /// Nest nodes under the heading that precedes them.
+    /// Each heading owns the following nodes until the next heading of equal or
+    /// higher level.
+    pub(crate) fn sectionize(&mut self) {
+        for node in &mut self.nodes {
+            node.sectionize();
+        }
+
+        if self.ordering != SectionOrdering::Set {
+            return;
+        }
+        if !self.nodes.iter().any(|n| n.heading_level().is_some()) {
+            return;
+        }
+
+        // Open sections, with the level of their heading.
+        let mut open: Vec<(u8, Section)> = Vec::new();
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes.drain(..) {
+            if let Some(level) = node.heading_level() {
+                while open.last().is_some_and(|(l, _)| *l >= level) {
+                    Self::close_section(&mut open, &mut nodes);
+                }
+                let title = match node.into_raw() {
+                    RawNode::Text(text) => text,
+                    _ => unreachable!("Only text has a heading level"),
+                };
+                open.push((level, Section::new_set().with_title(title)));
+                continue;
+            }
+            match open.last_mut() {
+                Some((_, section)) => section.nodes.push(node),
+                None => nodes.push(node),
+            }
+        }
+        while !open.is_empty() {
+            Self::close_section(&mut open, &mut nodes);
+        }
+        self.nodes = nodes;
+    }
+
+    /// Close the innermost open section, moving it into its parent.
+    fn close_section(open: &mut Vec<(u8, Section)>, nodes: &mut Vec<Node>) {
+        if let Some((_, section)) = open.pop() {
+            match open.last_mut() {
+                Some((_, parent)) => parent.nodes.push(Node::new(section.into())),
+                None => nodes.push(Node::new(section.into())),
+            }
+        }
+    }
 }
 
 /// Ordering for nodes in the section.
@@ -64,4 +182,60 @@ pub enum SectionOrdering {
     List,
     /// Ordered, enumerated items
     Enumeration,
+    /// Terms paired with descriptions.
+    Definition,
+}
+
+/// Semantic role of a section.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SectionKind {
+    /// Plain grouping without a role.
+    #[default]
+    Generic,
+    /// Self-contained content, like a post or story.
+    Article,
+    /// Navigation links.
+    Navigation,
+    /// Introductory content or site banner.
+    Header,
+    /// Footer content or site info.
+    Footer,
+    /// Content tangential to the main content.
+    Aside,
+    /// The main content of the document.
+    Main,
+}
+
+impl SectionKind {
+    /// Get the kind from an element name.
+    pub fn from_element(name: &str) -> Self {
+        match name {
+            "article" => Self::Article,
+            "nav" => Self::Navigation,
+            "header" => Self::Header,
+            "footer" => Self::Footer,
+            "aside" => Self::Aside,
+            "main" => Self::Main,
+            _ => Self::Generic,
+        }
+    }
+
+    /// Get the kind from an ARIA role, if the role maps to one.
+    pub fn from_role(role: &str) -> Option<Self> {
+        match role.trim().to_ascii_lowercase().as_str() {
+            "article" => Some(Self::Article),
+            "navigation" => Some(Self::Navigation),
+            "banner" => Some(Self::Header),
+            "contentinfo" => Some(Self::Footer),
+            "complementary" => Some(Self::Aside),
+            "main" => Some(Self::Main),
+            "region" | "group" | "presentation" | "none" => Some(Self::Generic),
+            _ => None,
+        }
+    }
+
+    /// Check if the section is page chrome, rather than content.
+    pub fn is_chrome(&self) -> bool {
+        matches!(self, Self::Navigation | Self::Header | Self::Footer)
+    }
 }
@@ -0,0 +1,262 @@
+use super::*;
+
+/// An RGB color.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Parse a CSS color: `#rgb`, `#rrggbb`, `rgb(r, g, b)` or a named color.
+    /// Alpha is ignored; `transparent` and `currentcolor` are not colors.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        if let Some(hex) = value.strip_prefix('#') {
+            return Self::parse_hex(hex);
+        }
+        if let Some(args) = value
+            .strip_prefix("rgba(")
+            .or(value.strip_prefix("rgb("))
+            .and_then(|v| v.strip_suffix(')'))
+        {
+            let mut channels = args
+                .split([',', ' ', '/'])
+                .filter(|c| !c.is_empty())
+                .map(Self::parse_channel);
+            return Some(Self::new(
+                channels.next()??,
+                channels.next()??,
+                channels.next()??,
+            ));
+        }
+        NAMED_COLORS
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, color)| *color)
+    }
+
+    fn parse_hex(hex: &str) -> Option<Self> {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok();
+        let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        match hex.len() {
+            3 | 4 => Some(Self::new(digit(0)? * 17, digit(1)? * 17, digit(2)? * 17)),
+            6 | 8 => Some(Self::new(pair(0)?, pair(2)?, pair(4)?)),
+            _ => None,
+        }
+    }
+
+    /// Parse a channel as a number or percentage.
+    fn parse_channel(channel: &str) -> Option<u8> {
+        let value = match channel.strip_suffix('%') {
+            Some(percent) => percent.parse::<f32>().ok()? * 2.55,
+            None => channel.parse::<f32>().ok()?,
+        };
+        Some(value.round().clamp(0.0, 255.0) as u8)
+    }
+}
+
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl From<Color> for String {
+    fn from(value: Color) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value).ok_or_else(|| format!("Invalid color: `{value}`"))
+    }
+}
+
+/// Split CSS declarations into lowercase properties and trimmed values.
+fn css_declarations(css: &str) -> impl Iterator<Item = (String, &str)> {
+    css.split(';').filter_map(|declaration| {
+        let (property, value) = declaration.split_once(':')?;
+        let value = value.trim().trim_end_matches("!important").trim();
+        Some((property.trim().to_ascii_lowercase(), value))
+    })
+}
+
+/// Check if CSS declarations hide an element, with `display: none` or
+/// `visibility: hidden`.
+pub(crate) fn css_hides(css: &str) -> bool {
+    css_declarations(css).any(|(property, value)| {
+        let value = value.to_ascii_lowercase();
+        match property.as_ref() {
+            "display" => value == "none",
+            "visibility" => value == "hidden" || value == "collapse",
+            _ => false,
+        }
+    })
+}
+
+/// Common CSS named colors.
+const NAMED_COLORS: &[(&str, Color)] = &[
+    ("black", Color::new(0, 0, 0)),
+    ("silver", Color::new(192, 192, 192)),
+    ("gray", Color::new(128, 128, 128)),
+    ("grey", Color::new(128, 128, 128)),
+    ("white", Color::new(255, 255, 255)),
+    ("maroon", Color::new(128, 0, 0)),
+    ("red", Color::new(255, 0, 0)),
+    ("purple", Color::new(128, 0, 128)),
+    ("fuchsia", Color::new(255, 0, 255)),
+    ("magenta", Color::new(255, 0, 255)),
+    ("green", Color::new(0, 128, 0)),
+    ("lime", Color::new(0, 255, 0)),
+    ("olive", Color::new(128, 128, 0)),
+    ("yellow", Color::new(255, 255, 0)),
+    ("navy", Color::new(0, 0, 128)),
+    ("blue", Color::new(0, 0, 255)),
+    ("teal", Color::new(0, 128, 128)),
+    ("aqua", Color::new(0, 255, 255)),
+    ("cyan", Color::new(0, 255, 255)),
+    ("orange", Color::new(255, 165, 0)),
+    ("brown", Color::new(165, 42, 42)),
+    ("pink", Color::new(255, 192, 203)),
+    ("gold", Color::new(255, 215, 0)),
+    ("darkred", Color::new(139, 0, 0)),
+    ("darkgreen", Color::new(0, 100, 0)),
+    ("darkblue", Color::new(0, 0, 139)),
+    ("darkorange", Color::new(255, 140, 0)),
+    ("lightgray", Color::new(211, 211, 211)),
+    ("lightgrey", Color::new(211, 211, 211)),
+    ("darkgray", Color::new(169, 169, 169)),
+    ("darkgrey", Color::new(169, 169, 169)),
+];
+
+/// Visual style of text, from inline `style` attributes or a class map.
+/// Unset fields leave the text unchanged.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextStyle {
+    /// Foreground color.
+    pub fg: Option<Color>,
+    /// Background color.
+    pub bg: Option<Color>,
+    /// Font weight, 100-900, where 400 is normal and 700 is bold.
+    pub weight: Option<u16>,
+    /// Italic font.
+    pub italic: Option<bool>,
+}
+
+impl TextStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fg(mut self, fg: Color) -> Self {
+        self.fg = Some(fg);
+        self
+    }
+
+    pub fn with_bg(mut self, bg: Color) -> Self {
+        self.bg = Some(bg);
+        self
+    }
+
+    pub fn with_weight(mut self, weight: u16) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn with_italic(mut self, italic: bool) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+
+    /// Parse CSS declarations, like `color: red; font-weight: bold`.
+    /// Unknown properties and values are ignored.
+    pub fn from_css(css: &str) -> Self {
+        let mut style = Self::new();
+        for (property, value) in css_declarations(css) {
+            match property.as_ref() {
+                "color" => style.fg = Color::parse(value).or(style.fg),
+                "background-color" => style.bg = Color::parse(value).or(style.bg),
+                // The shorthand may list an image or position with the color.
+                "background" => {
+                    style.bg = value.split_whitespace().find_map(Color::parse).or(style.bg)
+                }
+                "font-weight" => style.weight = Self::parse_weight(value).or(style.weight),
+                "font-style" => match value.to_ascii_lowercase().as_ref() {
+                    "italic" | "oblique" => style.italic = Some(true),
+                    "normal" => style.italic = Some(false),
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        style
+    }
+
+    fn parse_weight(value: &str) -> Option<u16> {
+        match value.to_ascii_lowercase().as_ref() {
+            "normal" => Some(400),
+            "bold" | "bolder" => Some(700),
+            "lighter" => Some(300),
+            number => number.parse().ok().filter(|w| (1..=1000).contains(w)),
+        }
+    }
+
+    /// Check if no fields are set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fill unset fields from another style.
+    pub fn merge(&mut self, other: &TextStyle) {
+        self.fg = self.fg.or(other.fg);
+        self.bg = self.bg.or(other.bg);
+        self.weight = self.weight.or(other.weight);
+        self.italic = self.italic.or(other.italic);
+    }
+
+    /// Apply the style to attributes that do not have their own.
+    /// Inner elements are styled first, so their styles, and their bold and
+    /// italic elements, take priority.
+    pub fn apply(&self, attributes: &mut TextAttributes) {
+        attributes.fg = attributes.fg.or(self.fg);
+        attributes.bg = attributes.bg.or(self.bg);
+        if let (None, false, Some(weight)) = (attributes.weight, attributes.bold, self.weight) {
+            attributes.weight = Some(weight);
+            attributes.bold = weight >= 600;
+        }
+        if let (None, false, Some(italic)) =
+            (attributes.italic_style, attributes.italic, self.italic)
+        {
+            attributes.italic_style = Some(italic);
+            attributes.italic = italic;
+        }
+    }
+
+    /// Get the style of an element from its `style` attribute, then its classes.
+    pub(crate) fn from_element(elem: &scraper::ElementRef, flags: &mut ParseContext) -> Self {
+        let mut style = flags
+            .attr(elem, "style")
+            .map(Self::from_css)
+            .unwrap_or_default();
+        for class in elem.value().classes() {
+            if let Some(class_style) = flags.class_styles.get(class) {
+                style.merge(class_style);
+            }
+        }
+        style
+    }
+}
@@ -1,8 +1,16 @@
-#[derive(Clone, Debug)]
+use super::*;
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Text {
     pub fragments: Vec<TextFragment>,
 }
 
+impl Default for Text {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Text {
     pub fn new() -> Self {
         Self {
@@ -22,6 +30,15 @@ impl Text {
         }
     }
 
+    /// Marker for content that was truncated at the depth limit.
+    pub fn truncated() -> Self {
+        let mut attributes = TextAttributes::new();
+        attributes.tooltip = Some("Truncated at the depth limit".into());
+        Self {
+            fragments: vec![TextFragment::new("[…]", Some(attributes))],
+        }
+    }
+
     /// Combine fragments, removing attributes.
     pub fn combine_fragments(&mut self) {
         // TODO: Handle combining additional attributes.
@@ -79,7 +96,7 @@ impl Text {
                 formatted = format!("{}({})", formatted, annotation);
             }
 
-            if total_formatted.len() > 0 {
+            if !total_formatted.is_empty() {
                 // total_formatted = format!("{total_formatted} {formatted}");
                 total_formatted = format!("{total_formatted}{formatted}");
             } else {
@@ -90,11 +107,31 @@ impl Text {
         total_formatted
     }
 
-    pub fn clean(&mut self) {
-        // TODO: Clean via parse flags.
-        for frag in self.fragments.iter_mut() {
-            frag.text = frag.text.replace("　", "");
+    /// Mark fragments without a highlight with a highlight category.
+    pub fn highlight(&mut self, name: &str) {
+        for frag in &mut self.fragments {
+            if frag.attributes.highlight.is_none() {
+                frag.attributes.highlight = Some(name.into());
+            }
+        }
+    }
+
+    /// Check if the text has no visible content.
+    pub fn is_blank(&self) -> bool {
+        self.fragments.iter().all(|f| f.text.trim().is_empty())
+    }
+
+    /// Heading level of the text, if every visible fragment is a heading.
+    pub fn heading_level(&self) -> Option<u8> {
+        let mut level: Option<u8> = None;
+        for frag in &self.fragments {
+            if frag.text.trim().is_empty() {
+                continue;
+            }
+            let heading = frag.attributes.heading?;
+            level = Some(level.map_or(heading, |l| l.min(heading)));
         }
+        level
     }
 
     pub fn collect(&self) -> String {
@@ -106,7 +143,7 @@ impl Text {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TextFragment {
     pub text: String,
     pub attributes: TextAttributes,
@@ -116,17 +153,14 @@ impl TextFragment {
     pub fn new(text: impl Into<String>, attributes: Option<TextAttributes>) -> Self {
         Self {
             text: text.into(),
-            attributes: match attributes {
-                Some(attr) => attr,
-                None => TextAttributes::default(),
-            },
+            attributes: attributes.unwrap_or_default(),
         }
     }
 
     #[allow(unused)]
     fn append(&mut self, text: impl AsRef<str>) {
         let trimmed = text.as_ref().trim();
-        if trimmed.len() > 0 {
+        if !trimmed.is_empty() {
             // self.text.push(' ');
             self.text.push_str(trimmed);
         }
@@ -158,7 +192,7 @@ impl AsRef<str> for TextFragment {
 }
 
 /// Attributes for a text fragment.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct TextAttributes {
     /// Preformatted, code, or mono font.
     pub preformatted: bool,
@@ -174,6 +208,25 @@ pub struct TextAttributes {
     pub tooltip: Option<String>,
     /// A text annotation.
     pub annotation: Option<String>,
+    /// Syntax class of highlighted code.
+    pub token: Option<TokenClass>,
+    /// Foreground color.
+    pub fg: Option<Color>,
+    /// Background color.
+    pub bg: Option<Color>,
+    /// Font weight, 100-900, if styled.
+    pub weight: Option<u16>,
+    /// Italic font style, if styled.
+    pub italic_style: Option<bool>,
+    /// Named highlight category from a semantic rule.
+    pub highlight: Option<String>,
+    /// Language tag, like `ja` or `en-US`.
+    /// Shared by the fragments that inherit it from the same element.
+    pub lang: Option<Arc<str>>,
+    /// Text direction.
+    pub dir: Option<Direction>,
+    /// Direction is forced by `bdo`, rather than resolved per character.
+    pub bidi_override: bool,
 }
 
 impl TextAttributes {
@@ -203,21 +256,47 @@ impl TextAttributes {
         if self.annotation.is_some() {
             return false;
         }
+        if self.token.is_some() {
+            return false;
+        }
+        if self.fg.is_some()
+            || self.bg.is_some()
+            || self.weight.is_some()
+            || self.italic_style.is_some()
+        {
+            return false;
+        }
+        if self.highlight.is_some() {
+            return false;
+        }
+        if self.lang.is_some() || self.dir.is_some() || self.bidi_override {
+            return false;
+        }
 
         true
     }
 }
 
-impl Default for TextAttributes {
-    fn default() -> Self {
-        Self {
-            preformatted: false,
-            italic: false,
-            bold: false,
-            heading: None,
-            link: None,
-            tooltip: None,
-            annotation: None,
-        }
-    }
+/// Syntax class of a highlighted code token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TokenClass {
+    Keyword,
+    /// Type or class name.
+    Type,
+    /// Built-in constant, like `true` or `null`.
+    Constant,
+    String,
+    Number,
+    Comment,
+    /// Function or macro name.
+    Function,
+    Variable,
+    /// Key of a mapping, like a JSON key or CSS property.
+    Property,
+    /// Markup tag or selector.
+    Tag,
+    /// Markup attribute.
+    Attribute,
+    Operator,
+    Punctuation,
 }
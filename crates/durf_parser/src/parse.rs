@@ -11,6 +11,43 @@ pub struct ParseFlags {
     pub parsing: bool,
     /// Remaining depth for the parse.
     pub remaining_depth: usize,
+    /// Styles applied to text with a class, by class name.
+    /// Inline `style` attributes take priority.
+    #[serde(default)]
+    pub class_styles: HashMap<String, TextStyle>,
+    /// Semantic rules for site-specific markup. The first matching rule applies.
+    #[serde(default)]
+    pub semantics: Vec<SemanticRule>,
+    /// Skip elements with the `hidden` attribute, or hidden by an inline
+    /// `display: none` or `visibility: hidden` style.
+    #[serde(default = "default_true")]
+    pub skip_hidden: bool,
+    /// Skip elements with `aria-hidden="true"`.
+    #[serde(default = "default_true")]
+    pub skip_aria_hidden: bool,
+    /// Skip `script`, `style`, and `template` elements.
+    #[serde(default = "default_true")]
+    pub skip_scripts: bool,
+    /// Use the content of `noscript` elements, since scripts are never run.
+    #[serde(default = "default_true")]
+    pub noscript: bool,
+    /// How elements past the depth limit are handled.
+    #[serde(default)]
+    pub depth_policy: DepthPolicy,
+    /// Limits on resources used by the parse.
+    #[serde(default)]
+    pub limits: ParseLimits,
+    /// Cleaning of text, like normalization and removal of invisible
+    /// characters.
+    #[serde(default)]
+    pub clean: CleanFlags,
+    /// Sanitizing of terminal control and bidi characters in text.
+    #[serde(default)]
+    pub sanitize: SanitizeFlags,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for ParseFlags {
@@ -20,47 +57,373 @@ impl Default for ParseFlags {
             skip: Vec::new(),
             parsing: true,
             remaining_depth: 10,
+            class_styles: HashMap::new(),
+            semantics: Vec::new(),
+            skip_hidden: true,
+            skip_aria_hidden: true,
+            skip_scripts: true,
+            noscript: true,
+            depth_policy: DepthPolicy::default(),
+            limits: ParseLimits::default(),
+            clean: CleanFlags::default(),
+            sanitize: SanitizeFlags::default(),
         }
     }
 }
 
 impl ParseFlags {
+    /// Style text with a class.
+    pub fn with_class_style(mut self, class: impl Into<String>, style: TextStyle) -> Self {
+        self.class_styles.insert(class.into(), style);
+        self
+    }
+
+    /// Check that all rules can match an element.
+    pub fn validate(&self) -> Result<(), Error> {
+        for rule in self.allow.iter().chain(&self.skip) {
+            rule.validate()?;
+        }
+        for rule in &self.semantics {
+            rule.rule.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Treat elements matching a rule with a semantic.
+    pub fn with_semantic(mut self, rule: ParseRule, semantic: Semantic) -> Self {
+        self.semantics.push(SemanticRule { rule, semantic });
+        self
+    }
+
+    pub(crate) fn semantic(&self, elem: &scraper::ElementRef) -> Option<&Semantic> {
+        self.semantics
+            .iter()
+            .find(|r| r.rule.matches(elem))
+            .map(|r| &r.semantic)
+    }
+
     pub(crate) fn should_parse(&self, elem: &scraper::ElementRef) -> bool {
         self.allow.iter().any(|r| r.matches(elem))
     }
 
     pub(crate) fn should_skip(&self, elem: &scraper::ElementRef) -> bool {
-        self.skip.iter().any(|r| r.matches(elem))
+        self.skip.iter().any(|r| r.matches(elem)) || self.is_hidden(elem)
+    }
+
+    /// Check if an element is never visible, according to the flags.
+    pub(crate) fn is_hidden(&self, elem: &scraper::ElementRef) -> bool {
+        if self.skip_scripts
+            && matches!(
+                elem.value().name.local.as_ref(),
+                "script" | "style" | "template"
+            )
+        {
+            return true;
+        }
+        if !self.noscript && elem.value().name.local.as_ref() == "noscript" {
+            return true;
+        }
+        if self.skip_hidden
+            && (elem.attr("hidden").is_some() || elem.attr("style").is_some_and(css_hides))
+        {
+            return true;
+        }
+        self.skip_aria_hidden
+            && elem
+                .attr("aria-hidden")
+                .is_some_and(|a| a.trim().eq_ignore_ascii_case("true"))
+    }
+}
+
+/// State of a parse, with the flags it was started with.
+pub(crate) struct ParseContext {
+    pub(crate) flags: ParseFlags,
+    /// Path of element names to the element being parsed.
+    pub(crate) path: Vec<String>,
+    /// Diagnostics collected during the parse.
+    pub(crate) report: ParseReport,
+    /// Time after which elements are no longer parsed.
+    pub(crate) deadline: Option<std::time::Instant>,
+    /// Language inherited by the element being parsed.
+    pub(crate) lang: Option<Arc<str>>,
+    /// Text direction inherited by the element being parsed.
+    pub(crate) dir: Option<Direction>,
+    /// Labels of form fields, collected when the first field is parsed.
+    pub(crate) labels: Option<FormLabels>,
+}
+
+impl ParseContext {
+    /// Start a parse, with the time limit starting now.
+    pub(crate) fn new(flags: ParseFlags) -> Self {
+        let mut context = Self {
+            flags,
+            path: Vec::new(),
+            report: ParseReport::default(),
+            deadline: None,
+            lang: None,
+            dir: None,
+            labels: None,
+        };
+        context.start_deadline(std::time::Duration::ZERO);
+        context
+    }
+
+    /// Start the time limit from now, less the time already spent parsing.
+    pub(crate) fn start_deadline(&mut self, spent: std::time::Duration) {
+        self.deadline = self
+            .limits
+            .max_parse_time
+            .map(|time| std::time::Instant::now() + time.saturating_sub(spent));
+    }
+
+    /// Inherit the language and direction declared on an element, until the
+    /// element is left. Returns the declared values.
+    pub(crate) fn inherit_language(
+        &mut self,
+        elem: &scraper::ElementRef,
+    ) -> (Option<String>, Option<Direction>) {
+        let (lang, dir) = Direction::from_element(elem, self);
+        if let Some(lang) = &lang {
+            self.lang = Some(lang.as_str().into());
+        }
+        self.dir = dir.or(self.dir);
+        (lang, dir)
+    }
+
+    /// Create a fragment with the inherited language and direction.
+    pub(crate) fn fragment(&self, text: &str) -> TextFragment {
+        let mut frag = TextFragment::from(text);
+        frag.attributes.lang = self.lang.clone();
+        frag.attributes.dir = self.dir;
+        frag
+    }
+
+    /// Set the inherited language and direction of the text of a node,
+    /// without its children, which inherit them as they are parsed.
+    pub(crate) fn set_language(&self, node: &mut RawNode) {
+        let text = match node {
+            RawNode::Text(text) => text,
+            RawNode::Code(code) => &mut code.text,
+            RawNode::Details(details) => &mut details.summary,
+            RawNode::Section(Section {
+                title: Some(title), ..
+            }) => title,
+            RawNode::Section(_) | RawNode::Empty | RawNode::Form(_) | RawNode::Field(_) => {
+                return;
+            }
+        };
+        text.set_language(self.lang.as_ref(), self.dir);
+    }
+
+    /// Path to an element, like `html > body > p`.
+    pub(crate) fn path_to(&self, name: &str) -> String {
+        let mut path = self.path.join(" > ");
+        if !path.is_empty() {
+            path.push_str(" > ");
+        }
+        path.push_str(name);
+        path
+    }
+
+    /// Record that an element exceeded the depth limit.
+    /// Flattened elements are recorded as fallbacks, since nothing is lost.
+    pub(crate) fn depth_exceeded(&mut self, name: &str) -> Error {
+        let path = self.path_to(name);
+        tracing::debug!("Depth exceeded at {path}");
+        match self.depth_policy {
+            DepthPolicy::Flatten => self.report.fallbacks.push(ParseFallback {
+                path: path.clone(),
+                kind: FallbackKind::Flattened,
+            }),
+            _ => self.report.depth_hits.push(path.clone()),
+        }
+        Error::DepthExceeded {
+            element: name.into(),
+            path,
+        }
+    }
+
+    /// Record that a resource limit was reached at the current element.
+    pub(crate) fn hit_limit(&mut self, limit: Limit) {
+        let path = self.path.join(" > ");
+        self.report.hit_limit(limit, path);
+    }
+
+    /// Check if the node count or parse time is exhausted, so no more
+    /// elements are parsed.
+    pub(crate) fn is_exhausted(&mut self) -> bool {
+        if self.report.nodes >= self.limits.max_nodes {
+            self.hit_limit(Limit::Nodes);
+            return true;
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+        {
+            self.hit_limit(Limit::ParseTime);
+            return true;
+        }
+        false
+    }
+
+    /// Get an attribute of an element, ignoring values over the limit.
+    pub(crate) fn attr<'a>(
+        &mut self,
+        elem: &scraper::ElementRef<'a>,
+        name: &str,
+    ) -> Option<&'a str> {
+        let value = elem.attr(name)?;
+        if value.len() > self.limits.max_attribute_bytes {
+            self.hit_limit(Limit::AttributeBytes);
+            return None;
+        }
+        Some(value)
+    }
+
+    /// Take text within the fragment and total text limits, truncating it.
+    pub(crate) fn take_text<'a>(&mut self, text: &'a str) -> &'a str {
+        let mut max = self.limits.max_fragment_bytes;
+        if text.len() > max {
+            self.hit_limit(Limit::FragmentBytes);
+        }
+        let remaining = self
+            .limits
+            .max_text_bytes
+            .saturating_sub(self.report.text_bytes);
+        if text.len().min(max) > remaining {
+            self.hit_limit(Limit::TextBytes);
+            max = remaining;
+        }
+        let text = truncate_str(text, max);
+        self.report.text_bytes += text.len();
+        text
+    }
+
+    /// Take the trimmed text of an element within the text limits.
+    pub(crate) fn element_text(&mut self, elem: &scraper::ElementRef) -> String {
+        let text: String = elem.text().collect();
+        self.take_text(text.trim()).into()
+    }
+
+    /// Record a fallback at the current element.
+    pub(crate) fn record_fallback(&mut self, kind: FallbackKind) {
+        let path = self.path.join(" > ");
+        self.report.fallbacks.push(ParseFallback { path, kind });
+    }
+}
+
+impl std::ops::Deref for ParseContext {
+    type Target = ParseFlags;
+
+    fn deref(&self) -> &Self::Target {
+        &self.flags
     }
 }
 
+impl std::ops::DerefMut for ParseContext {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.flags
+    }
+}
+
+/// Limits on resources used by a parse, to bound memory and time on hostile
+/// documents. Content past a limit is truncated and reported in
+/// [`ParseReport::limit_hits`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParseLimits {
+    /// Maximum bytes of the document. Later input is not parsed.
+    pub max_input_bytes: usize,
+    /// Maximum number of nodes. Later elements are skipped.
+    pub max_nodes: usize,
+    /// Maximum bytes of text in total. Later text is truncated.
+    pub max_text_bytes: usize,
+    /// Maximum bytes of a single text fragment. Longer text is truncated.
+    pub max_fragment_bytes: usize,
+    /// Maximum bytes of an attribute value. Longer values are ignored, as if
+    /// the attribute were missing.
+    pub max_attribute_bytes: usize,
+    /// Maximum time spent parsing elements. Later elements are skipped.
+    pub max_parse_time: Option<std::time::Duration>,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_input_bytes: 32 * 1024 * 1024,
+            max_nodes: 200_000,
+            max_text_bytes: 16 * 1024 * 1024,
+            max_fragment_bytes: 256 * 1024,
+            max_attribute_bytes: 16 * 1024,
+            max_parse_time: Some(std::time::Duration::from_secs(10)),
+        }
+    }
+}
+
+/// Truncate a string to at most `max` bytes, on a character boundary.
+pub(crate) fn truncate_str(text: &str, max: usize) -> &str {
+    if text.len() <= max {
+        return text;
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Handling of elements past the depth limit.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DepthPolicy {
+    /// Fail to parse the element, dropping it from its parent.
+    #[serde(alias = "error")]
+    Error,
+    /// Replace the element with a marker.
+    #[serde(alias = "truncate")]
+    Truncate,
+    /// Parse the text of the element into its parent, without nesting.
+    #[default]
+    #[serde(alias = "flatten")]
+    Flatten,
+}
+
 /// Simply recusion with guard for parse flags.
-pub(crate) struct DepthGuard<'a>(&'a mut ParseFlags);
+/// Tracks the path to the element while it is parsed, and restores the
+/// inherited language when it is left.
+pub(crate) struct DepthGuard<'a> {
+    flags: &'a mut ParseContext,
+    language: (Option<Arc<str>>, Option<Direction>),
+}
 
 impl<'a> DepthGuard<'a> {
-    pub(crate) fn new(flags: &'a mut ParseFlags) -> Self {
+    pub(crate) fn new(flags: &'a mut ParseContext, name: &str) -> Self {
         flags.remaining_depth = flags.remaining_depth.saturating_sub(1);
-        Self(flags)
+        flags.path.push(name.into());
+        flags.report.elements += 1;
+        let language = (flags.lang.clone(), flags.dir);
+        Self { flags, language }
     }
 }
 
 impl<'a> Drop for DepthGuard<'a> {
     fn drop(&mut self) {
         self.remaining_depth = self.remaining_depth.saturating_add(1);
+        self.path.pop();
+        (self.flags.lang, self.flags.dir) = std::mem::take(&mut self.language);
     }
 }
 
 impl<'a> std::ops::Deref for DepthGuard<'a> {
-    type Target = ParseFlags;
+    type Target = ParseContext;
 
     fn deref(&self) -> &Self::Target {
-        self.0
+        self.flags
     }
 }
 
 impl<'a> std::ops::DerefMut for DepthGuard<'a> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        self.flags
     }
 }
 
@@ -84,16 +447,87 @@ impl ParseRule {
         Self::Class(class.into())
     }
 
+    /// Check that the rule can match an element.
+    pub fn validate(&self) -> Result<(), Error> {
+        let reason = match self {
+            ParseRule::Element(e) if e.is_empty() => Some("element name is empty"),
+            ParseRule::Element(e)
+                if !e
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') =>
+            {
+                Some("element names are lowercase letters, digits, and `-`")
+            }
+            ParseRule::Class(c) if c.is_empty() => Some("class name is empty"),
+            ParseRule::Class(c) if c.contains(char::is_whitespace) => {
+                Some("class names cannot contain whitespace")
+            }
+            _ => None,
+        };
+        match reason {
+            Some(reason) => Err(Error::InvalidRule {
+                rule: format!("{self:?}"),
+                reason: reason.into(),
+            }),
+            None => Ok(()),
+        }
+    }
+
     /// Check if parse rule matches element.
     fn matches(&self, elem: &scraper::ElementRef) -> bool {
-        match &self {
-            &ParseRule::Element(e) => {
+        match self {
+            ParseRule::Element(e) => {
                 let ele_name = elem.value().name.local.to_lowercase();
                 ele_name == *e
             }
-            &ParseRule::Class(c) => elem
+            ParseRule::Class(c) => elem
                 .value()
                 .has_class(c.as_str(), scraper::CaseSensitivity::AsciiCaseInsensitive),
         }
     }
 }
+
+/// Semantic effect for elements matching a rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Semantic {
+    /// Parse as a heading of a level, 1-6.
+    #[serde(alias = "heading")]
+    Heading(u8),
+    /// Parse as inline code.
+    #[serde(alias = "code")]
+    Code,
+    /// Parse as a quote.
+    #[serde(alias = "quote")]
+    Quote,
+    /// Parse as an annotation, like ruby text.
+    #[serde(alias = "annotation")]
+    Annotation,
+    /// Skip with descendents.
+    #[serde(alias = "hidden")]
+    Hidden,
+    /// Mark text with a named highlight category.
+    #[serde(alias = "highlight")]
+    Highlight(String),
+}
+
+impl Semantic {
+    /// Element that matched elements are parsed as, if replaced.
+    pub(crate) fn element_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Heading(level) => {
+                Some(["h1", "h2", "h3", "h4", "h5", "h6"][(*level).clamp(1, 6) as usize - 1])
+            }
+            Self::Code => Some("code"),
+            Self::Quote => Some("blockquote"),
+            Self::Annotation => Some("rt"),
+            Self::Hidden | Self::Highlight(_) => None,
+        }
+    }
+}
+
+/// A rule giving matched elements a semantic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticRule {
+    pub rule: ParseRule,
+    pub semantic: Semantic,
+}
@@ -2,14 +2,30 @@
 
 #![allow(unused)]
 
+pub use crate::arena::*;
 pub use crate::ast::Ast;
+pub use crate::builder::*;
+pub use crate::clean::*;
+pub use crate::diff::*;
+pub use crate::encoding::{DetectedEncoding, EncodingSource};
 pub use crate::error::*;
+#[cfg(feature = "highlight")]
+pub use crate::highlight::is_language_supported;
 pub use crate::nodes::*;
 pub use crate::nodes::*;
 pub use crate::parse::*;
+pub use crate::report::*;
+pub use crate::sanitize::*;
+pub use crate::search::*;
+pub use crate::stream::StreamParser;
 
 pub(crate) mod internal {
-    pub use std::{ops::DerefMut, rc::Rc, sync::Arc};
+    pub use std::{
+        collections::{BTreeMap, HashMap, HashSet},
+        ops::DerefMut,
+        rc::Rc,
+        sync::Arc,
+    };
 
     pub use scraper::Element;
     pub use serde::{Deserialize, Serialize};
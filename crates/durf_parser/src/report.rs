@@ -0,0 +1,159 @@
+use super::*;
+
+/// Diagnostics collected while parsing a document.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ParseReport {
+    /// Size of the parsed document in bytes.
+    pub input_bytes: usize,
+    /// Encoding the document was decoded with, if parsed from bytes.
+    pub encoding: Option<DetectedEncoding>,
+    /// Number of elements visited.
+    pub elements: usize,
+    /// Number of nodes in the parsed AST.
+    pub nodes: usize,
+    /// Unsupported elements that were dropped, with counts by name.
+    pub dropped: BTreeMap<String, usize>,
+    /// Elements skipped by parse flags or because they are never visible,
+    /// with counts by name.
+    pub skipped: BTreeMap<String, usize>,
+    /// Paths of elements dropped or truncated for exceeding the depth limit.
+    pub depth_hits: Vec<String>,
+    /// Places where the parser fell back to a default interpretation.
+    pub fallbacks: Vec<ParseFallback>,
+    /// Bytes of text parsed.
+    pub text_bytes: usize,
+    /// Resource limits that were reached, once per limit.
+    pub limit_hits: Vec<LimitHit>,
+}
+
+impl ParseReport {
+    /// Total number of elements that are not rendered, either dropped or
+    /// past the depth limit.
+    pub fn not_rendered(&self) -> usize {
+        self.dropped.values().sum::<usize>() + self.depth_hits.len()
+    }
+
+    /// Check if every element was parsed as-is.
+    pub fn is_clean(&self) -> bool {
+        self.dropped.is_empty()
+            && self.depth_hits.is_empty()
+            && self.fallbacks.is_empty()
+            && self.limit_hits.is_empty()
+    }
+
+    /// Get the hit of a limit, if it was reached.
+    pub fn limit_hit(&self, limit: Limit) -> Option<&LimitHit> {
+        self.limit_hits.iter().find(|h| h.limit == limit)
+    }
+
+    pub(crate) fn hit_limit(&mut self, limit: Limit, path: String) {
+        match self.limit_hits.iter_mut().find(|h| h.limit == limit) {
+            Some(hit) => hit.count += 1,
+            None => {
+                tracing::debug!("Reached {limit} limit at {path}");
+                self.limit_hits.push(LimitHit {
+                    limit,
+                    path,
+                    count: 1,
+                });
+            }
+        }
+    }
+
+    pub(crate) fn drop_element(&mut self, name: &str) {
+        *self.dropped.entry(name.into()).or_default() += 1;
+    }
+
+    pub(crate) fn skip_element(&mut self, name: &str) {
+        *self.skipped.entry(name.into()).or_default() += 1;
+    }
+}
+
+impl std::fmt::Display for ParseReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Parsed {} bytes, {} elements into {} nodes",
+            self.input_bytes, self.elements, self.nodes
+        )?;
+        if let Some(encoding) = &self.encoding {
+            writeln!(f, "Decoded as {} from {:?}", encoding.name, encoding.source)?;
+        }
+        for (name, count) in &self.dropped {
+            writeln!(f, "Dropped <{name}> x{count}")?;
+        }
+        for (name, count) in &self.skipped {
+            writeln!(f, "Skipped <{name}> x{count}")?;
+        }
+        for path in &self.depth_hits {
+            writeln!(f, "Depth exceeded at {path}")?;
+        }
+        for fallback in &self.fallbacks {
+            writeln!(f, "Fallback at {}: {:?}", fallback.path, fallback.kind)?;
+        }
+        for hit in &self.limit_hits {
+            writeln!(
+                f,
+                "Reached {} limit at {} x{}",
+                hit.limit, hit.path, hit.count
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A default interpretation made while parsing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseFallback {
+    /// Path of the element, like `html > body > p`.
+    pub path: String,
+    pub kind: FallbackKind,
+}
+
+/// Kind of default interpretation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FallbackKind {
+    /// An unsupported element in text was kept as plain text.
+    PlainText,
+    /// A `details` element without a `summary` got a default summary.
+    DefaultSummary,
+    /// `noscript` content was parsed again as markup.
+    NoscriptMarkup,
+    /// An element past the depth limit was flattened into text.
+    Flattened,
+}
+
+/// A resource limit that was reached.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LimitHit {
+    pub limit: Limit,
+    /// Path of the element where the limit was first reached.
+    pub path: String,
+    /// Number of times content was truncated by the limit.
+    pub count: usize,
+}
+
+/// A resource limit of a parse.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Limit {
+    InputBytes,
+    Nodes,
+    TextBytes,
+    FragmentBytes,
+    AttributeBytes,
+    ParseTime,
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::InputBytes => "input bytes",
+            Self::Nodes => "node count",
+            Self::TextBytes => "text bytes",
+            Self::FragmentBytes => "fragment bytes",
+            Self::AttributeBytes => "attribute bytes",
+            Self::ParseTime => "parse time",
+        };
+        write!(f, "{name}")
+    }
+}
@@ -0,0 +1,170 @@
+use super::*;
+
+use std::borrow::Cow;
+
+/// How unsafe characters in text are sanitized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SanitizeFlags {
+    /// C0 and C1 control characters, except tab and line feed.
+    pub controls: SanitizeMode,
+    /// Bidi embedding, override, isolate, and mark characters.
+    pub bidi: SanitizeMode,
+}
+
+impl Default for SanitizeFlags {
+    fn default() -> Self {
+        Self {
+            controls: SanitizeMode::Escape,
+            bidi: SanitizeMode::Strip,
+        }
+    }
+}
+
+/// Handling of a class of unsafe characters.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SanitizeMode {
+    /// Remove the characters.
+    #[serde(alias = "strip")]
+    Strip,
+    /// Replace the characters with a visible escape, like `␛` or `<U+202E>`.
+    #[serde(alias = "escape")]
+    Escape,
+    /// Keep the characters. Only use this for trusted documents.
+    #[serde(alias = "keep")]
+    Keep,
+}
+
+/// Check if a character is a control character that can manipulate a
+/// terminal. Tab and line feed are allowed.
+pub fn is_unsafe_control(c: char) -> bool {
+    c.is_control() && c != '\t' && c != '\n'
+}
+
+/// Check if a character is a bidi embedding, override, isolate, or mark,
+/// which can reorder surrounding text.
+pub fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+/// Sanitize unsafe characters in text.
+/// Returns the text unchanged, without allocating, if it is safe.
+pub fn sanitize<'a>(text: &'a str, flags: &SanitizeFlags) -> Cow<'a, str> {
+    let mode = |c: char| {
+        if is_unsafe_control(c) {
+            Some(flags.controls)
+        } else if is_bidi_control(c) {
+            Some(flags.bidi)
+        } else {
+            None
+        }
+    };
+    let needs_sanitizing = |c: char| !matches!(mode(c), None | Some(SanitizeMode::Keep));
+    if !text.chars().any(needs_sanitizing) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut sanitized = String::with_capacity(text.len());
+    for c in text.chars() {
+        match mode(c) {
+            None | Some(SanitizeMode::Keep) => sanitized.push(c),
+            Some(SanitizeMode::Strip) => {}
+            Some(SanitizeMode::Escape) => match c {
+                // Control pictures, like `␛` for escape.
+                '\0'..='\u{1F}' => {
+                    sanitized.push(char::from_u32(0x2400 + c as u32).unwrap_or('\u{FFFD}'))
+                }
+                '\u{7F}' => sanitized.push('␡'),
+                _ => sanitized.push_str(&format!("<U+{:04X}>", c as u32)),
+            },
+        }
+    }
+    Cow::Owned(sanitized)
+}
+
+/// Sanitize a string in place.
+pub(crate) fn sanitize_string(text: &mut String, flags: &SanitizeFlags) {
+    if let Cow::Owned(sanitized) = sanitize(text, flags) {
+        *text = sanitized;
+    }
+}
+
+impl Text {
+    /// Sanitize unsafe characters in the text and its attributes.
+    pub fn sanitize(&mut self, flags: &SanitizeFlags) {
+        for frag in &mut self.fragments {
+            sanitize_string(&mut frag.text, flags);
+            let attributes = &mut frag.attributes;
+            for value in [
+                &mut attributes.link,
+                &mut attributes.tooltip,
+                &mut attributes.annotation,
+                &mut attributes.highlight,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                sanitize_string(value, flags);
+            }
+            // The language is shared between fragments, so it is only
+            // replaced if it changes.
+            if let Some(lang) = &mut attributes.lang {
+                if let Cow::Owned(sanitized) = sanitize(lang, flags) {
+                    *lang = sanitized.into();
+                }
+            }
+        }
+    }
+}
+
+impl RawNode {
+    /// Sanitize unsafe characters in all text of the node and its descendents.
+    pub fn sanitize(&mut self, flags: &SanitizeFlags) {
+        self.for_each_text_mut(&mut |text| text.sanitize(flags));
+        self.sanitize_fields(flags);
+    }
+
+    /// Sanitize strings outside of text, like form fields.
+    fn sanitize_fields(&mut self, flags: &SanitizeFlags) {
+        match self {
+            Self::Form(form) => {
+                if let Some(action) = &mut form.action {
+                    sanitize_string(action, flags);
+                }
+                sanitize_string(&mut form.enctype, flags);
+            }
+            Self::Section(section) => {
+                if let Some(lang) = &mut section.lang {
+                    sanitize_string(lang, flags);
+                }
+            }
+            Self::Field(field) => {
+                for value in [&mut field.name, &mut field.label, &mut field.placeholder]
+                    .into_iter()
+                    .flatten()
+                {
+                    sanitize_string(value, flags);
+                }
+                sanitize_string(&mut field.value, flags);
+                if let FieldKind::Select(options) = &mut field.kind {
+                    for option in options {
+                        sanitize_string(&mut option.value, flags);
+                        sanitize_string(&mut option.label, flags);
+                    }
+                }
+            }
+            Self::Code(code) => {
+                if let Some(language) = &mut code.language {
+                    sanitize_string(language, flags);
+                }
+            }
+            _ => {}
+        }
+        for node in self.children_mut() {
+            node.sanitize_fields(flags);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Full-text search of a document.
+
+use super::*;
+
+use std::ops::Range;
+use unicode_normalization::UnicodeNormalization;
+
+/// Options for searching an AST.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchOptions {
+    /// Match case exactly.
+    pub case_sensitive: bool,
+    /// Only match whole words.
+    pub whole_word: bool,
+    /// Treat the query as a regular expression.
+    pub regex: bool,
+    /// Match hiragana with katakana, and halfwidth and fullwidth forms with
+    /// each other, by compatibility decomposition.
+    pub ignore_kana_and_width: bool,
+}
+
+/// A match of a search, which may span several fragments of a text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// Path to the node with the matched text.
+    pub path: NodePath,
+    /// Whether the match is in the title of a section or the summary of a
+    /// disclosure, rather than the text of the node.
+    pub in_title: bool,
+    /// Matched parts of each fragment, in order.
+    pub spans: Vec<FragmentSpan>,
+}
+
+/// Part of a text fragment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FragmentSpan {
+    /// Index of the fragment in the text.
+    pub fragment: usize,
+    /// Range of characters in the fragment.
+    pub chars: Range<usize>,
+}
+
+impl Ast {
+    /// Find text matching a query, in document order.
+    pub fn search(&self, query: &str, options: &SearchOptions) -> Result<Vec<SearchMatch>, Error> {
+        let mut matches = Vec::new();
+        if query.is_empty() {
+            return Ok(matches);
+        }
+        let regex = search_regex(query, options)?;
+        search_node(&self.root, NodePath::root(), &regex, options, &mut matches);
+        Ok(matches)
+    }
+}
+
+/// Compile the query into a regular expression.
+fn search_regex(query: &str, options: &SearchOptions) -> Result<regex::Regex, Error> {
+    let folded = match options.ignore_kana_and_width {
+        true => fold(query).into_iter().map(|(c, _)| c).collect(),
+        false => query.to_string(),
+    };
+    let mut pattern = match options.regex {
+        true => folded,
+        false => regex::escape(&folded),
+    };
+    if options.whole_word {
+        pattern = format!(r"\b(?:{pattern})\b");
+    }
+    regex::RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .build()
+        .map_err(|e| Error::InvalidQuery {
+            query: query.into(),
+            reason: e.to_string(),
+        })
+}
+
+/// Search a node and its descendents.
+fn search_node(
+    node: &RawNode,
+    path: NodePath,
+    regex: &regex::Regex,
+    options: &SearchOptions,
+    matches: &mut Vec<SearchMatch>,
+) {
+    let (text, in_title) = match node {
+        RawNode::Text(text) => (Some(text), false),
+        RawNode::Code(code) => (Some(&code.text), false),
+        RawNode::Section(section) => (section.title.as_ref(), true),
+        RawNode::Details(details) => (Some(&details.summary), true),
+        RawNode::Empty | RawNode::Form(_) | RawNode::Field(_) => (None, false),
+    };
+    if let Some(text) = text {
+        for spans in search_text(text, regex, options) {
+            matches.push(SearchMatch {
+                path: path.clone(),
+                in_title,
+                spans,
+            });
+        }
+    }
+    for (index, child) in node.children().iter().enumerate() {
+        search_node(child, path.child(index), regex, options, matches);
+    }
+}
+
+/// Find matches in the fragments of a text.
+fn search_text(
+    text: &Text,
+    regex: &regex::Regex,
+    options: &SearchOptions,
+) -> Vec<Vec<FragmentSpan>> {
+    // Characters searched, with the index of the character of the text each
+    // came from.
+    let chars: Vec<(char, usize)> = match options.ignore_kana_and_width {
+        true => fold(&text.collect()),
+        false => text.collect().chars().zip(0..).collect(),
+    };
+    let haystack: String = chars.iter().map(|(c, _)| c).collect();
+    // Index of the character at each byte of the haystack.
+    let mut char_at = Vec::with_capacity(haystack.len() + 1);
+    for (index, (c, _)) in chars.iter().enumerate() {
+        char_at.extend(std::iter::repeat_n(index, c.len_utf8()));
+    }
+    char_at.push(chars.len());
+
+    // Characters of the text at the start of each fragment.
+    let mut starts = Vec::with_capacity(text.fragments.len() + 1);
+    let mut total = 0;
+    for frag in &text.fragments {
+        starts.push(total);
+        total += frag.text.chars().count();
+    }
+    starts.push(total);
+
+    regex
+        .find_iter(&haystack)
+        .filter(|m| !m.is_empty())
+        .map(|m| {
+            let start = chars[char_at[m.start()]].1;
+            let end = chars[char_at[m.end()] - 1].1 + 1;
+            (0..text.fragments.len())
+                .filter_map(|fragment| {
+                    let chars = start.max(starts[fragment])..end.min(starts[fragment + 1]);
+                    (!chars.is_empty()).then(|| FragmentSpan {
+                        fragment,
+                        chars: chars.start - starts[fragment]..chars.end - starts[fragment],
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Fold text for kana and width insensitive matching, keeping the index of
+/// the character each folded character came from.
+fn fold(text: &str) -> Vec<(char, usize)> {
+    let mut folded = Vec::with_capacity(text.len());
+    for (index, c) in text.chars().enumerate() {
+        for c in std::iter::once(c).nfkd() {
+            // Katakana to hiragana.
+            let c = match c {
+                'ァ'..='ヶ' | 'ヽ' | 'ヾ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+                c => c,
+            };
+            folded.push((c, index));
+        }
+    }
+    folded
+}
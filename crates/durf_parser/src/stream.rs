@@ -0,0 +1,478 @@
+//! Incremental parsing of HTML as it arrives.
+
+use super::*;
+
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use html5ever::tendril::{StrTendril, TendrilSink};
+use html5ever::tree_builder::{ElementFlags, NodeOrText, QuirksMode, TreeSink};
+use html5ever::{Attribute, QualName};
+
+/// An incremental HTML parser.
+///
+/// Chunks of bytes are decoded and fed to the HTML tokenizer as they arrive,
+/// and each child of the `body` is parsed into a node as soon as the next
+/// child starts, so the top of a page can be shown before the rest is
+/// downloaded. A lone wrapper, like `<div id="app">`, is descended into, so
+/// its children are parsed as they finish too.
+///
+/// The encoding is detected like [`Ast::from_html_bytes`], from the first
+/// bytes of the document, so nothing is parsed until enough bytes arrive to
+/// find a `<meta>` declaration, unless the content type declares one.
+/// The parse time limit applies to the time spent parsing the whole stream,
+/// not counting the time waiting for chunks.
+pub struct StreamParser {
+    parser: html5ever::driver::Parser<StreamSink>,
+    /// The document, shared with the parser.
+    html: Rc<scraper::HtmlTreeSink>,
+    body: BodyParser,
+    content_type: Option<String>,
+    /// Decoder of the stream, once the encoding is detected.
+    decoder: Option<(encoding_rs::Decoder, DetectedEncoding)>,
+    /// Bytes held until the encoding is detected.
+    head: Vec<u8>,
+    input_bytes: usize,
+    /// Time spent parsing the chunks so far.
+    spent: Duration,
+}
+
+/// State of parsing the children of the body.
+struct BodyParser {
+    flags: ParseContext,
+    /// Whether the path and depth of `html > body` have been entered.
+    entered: bool,
+    /// The body, then each lone wrapper within it, innermost last.
+    levels: Vec<Level>,
+    /// Number of nodes of the document scanned for form labels.
+    scanned: usize,
+}
+
+/// An element whose children are parsed as they finish.
+struct Level {
+    id: ego_tree::NodeId,
+    /// Number of child nodes already parsed.
+    parsed: usize,
+    /// Section of the element, with the children parsed so far.
+    section: Section,
+    /// Inherited language and direction, and parsing condition, restored
+    /// when the element is left.
+    restore: (Option<Arc<str>>, Option<Direction>, bool),
+}
+
+impl StreamParser {
+    /// Start parsing a document, with the content type it was served with.
+    pub fn new(content_type: Option<&str>, flags: ParseFlags) -> Result<Self, Error> {
+        flags.validate()?;
+        let html = Rc::new(scraper::HtmlTreeSink::new(scraper::Html::new_document()));
+        let sink = StreamSink(Rc::clone(&html));
+        Ok(Self {
+            parser: html5ever::driver::parse_document(sink, Default::default()),
+            html,
+            body: BodyParser {
+                flags: ParseContext::new(flags),
+                entered: false,
+                levels: Vec::new(),
+                scanned: 0,
+            },
+            content_type: content_type.map(String::from),
+            decoder: None,
+            head: Vec::new(),
+            input_bytes: 0,
+            spent: Duration::ZERO,
+        })
+    }
+
+    /// Feed a chunk of the document, returning the nodes it finished.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<Node>, Error> {
+        let flags = &mut self.body.flags;
+        let remaining = flags
+            .limits
+            .max_input_bytes
+            .saturating_sub(self.input_bytes);
+        let accepted = &chunk[..chunk.len().min(remaining)];
+        self.input_bytes += chunk.len();
+        if accepted.len() < chunk.len() && flags.report.limit_hit(Limit::InputBytes).is_none() {
+            flags.hit_limit(Limit::InputBytes);
+        }
+
+        let text = self.decode(accepted, false)?;
+        if text.is_empty() {
+            return Ok(Vec::new());
+        }
+        let start = Instant::now();
+        self.body.flags.start_deadline(self.spent);
+        self.parser.process(text.into());
+        // The last child may still be open.
+        let nodes = self.body.parse(&self.html.0.borrow(), true);
+        self.spent += start.elapsed();
+        Ok(nodes)
+    }
+
+    /// Finish the document, returning the remaining nodes and the AST of
+    /// every node, with the children of the body in its root.
+    pub fn finish(mut self) -> Result<(Vec<Node>, Ast), Error> {
+        let text = self.decode(&[], true)?;
+        self.body.flags.start_deadline(self.spent);
+        self.parser.process(text.into());
+        self.parser.finish();
+        let html = self.html.0.borrow();
+        let mut body = self.body;
+        let remaining = body.parse(&html, false);
+
+        let mut flags = body.flags;
+        let (lang, dir) = match html.root_element().value().name.local.as_ref() {
+            "html" => Direction::from_element(&html.root_element(), &mut flags),
+            _ => (None, None),
+        };
+        // Every wrapper is left, so only the body remains.
+        let mut root = Section::new_set();
+        if let Some(level) = body.levels.pop() {
+            root.nodes = level.section.nodes;
+        }
+        root.lang = lang.clone();
+        root.dir = dir;
+        let root = Node::new(root.into());
+        let mut report = std::mem::take(&mut flags.report);
+        report.input_bytes = self.input_bytes;
+        report.nodes = root.node_count();
+        report.encoding = self.decoder.map(|(_, encoding)| encoding);
+        Ok((
+            remaining,
+            Ast {
+                root,
+                lang: lang.map(Arc::from),
+                dir,
+                report: Arc::new(report),
+            },
+        ))
+    }
+
+    /// Decode bytes of the stream, holding them until the encoding is
+    /// detected.
+    fn decode(&mut self, bytes: &[u8], last: bool) -> Result<String, Error> {
+        let mut bytes = bytes;
+        let head;
+        if self.decoder.is_none() {
+            self.head.extend_from_slice(bytes);
+            // Only a byte order mark overrides a declared encoding.
+            let needed = match crate::encoding::is_declared(self.content_type.as_deref()) {
+                true => 3,
+                false => crate::encoding::PRESCAN_BYTES,
+            };
+            if !last && self.head.len() < needed {
+                return Ok(String::new());
+            }
+            self.decoder = Some(crate::encoding::decoder(
+                &self.head,
+                self.content_type.as_deref(),
+            )?);
+            head = std::mem::take(&mut self.head);
+            bytes = &head;
+        }
+        let Some((decoder, encoding)) = &mut self.decoder else {
+            return Ok(String::new());
+        };
+        let capacity = decoder
+            .max_utf8_buffer_length(bytes.len())
+            .unwrap_or(bytes.len() * 3);
+        let mut text = String::with_capacity(capacity);
+        let (_, _, had_errors) = decoder.decode_to_string(bytes, &mut text, last);
+        encoding.had_errors |= had_errors;
+        // A byte order mark may have changed the encoding.
+        encoding.name = decoder.encoding().name().into();
+        Ok(text)
+    }
+}
+
+impl BodyParser {
+    /// Parse children of the body that have not been parsed yet.
+    fn parse(&mut self, html: &scraper::Html, keep_last: bool) -> Vec<Node> {
+        let flags = &mut self.flags;
+        // Labels may have arrived since the last chunk.
+        let labels = flags.labels.get_or_insert_with(FormLabels::default);
+        labels.extend(html.tree.nodes().skip(self.scanned));
+        self.scanned = html.tree.nodes().len();
+        let root = html.root_element();
+        let Some(body) = root
+            .child_elements()
+            .find(|e| e.value().name.local.as_ref() == "body")
+        else {
+            return Vec::new();
+        };
+        if !self.entered {
+            self.entered = true;
+            for elem in [root, body] {
+                self.levels.push(Self::enter(&elem, flags));
+            }
+            // The root takes the place of the `html` element.
+            self.levels.remove(0);
+        }
+
+        let mut nodes = Vec::new();
+        let mut depth = 0;
+        while let Some(level) = self.levels.get(depth) {
+            let Some(elem) = html.tree.get(level.id).and_then(scraper::ElementRef::wrap) else {
+                break;
+            };
+            let children: Vec<_> = elem.children().collect();
+            let inner = self.levels.get(depth + 1).map(|inner| inner.id);
+            let finished = match keep_last {
+                true => children.len().saturating_sub(1),
+                false => children.len(),
+            };
+            let parsed = self.levels[depth].parsed;
+            for (index, child) in children.iter().enumerate().take(finished).skip(parsed) {
+                if Some(child.id()) == inner {
+                    // A wrapper with later siblings is finished.
+                    self.finish_level(depth + 1, html, &mut nodes);
+                } else if let Some(node) = Self::parse_child(child, &mut self.flags) {
+                    nodes.push(node.clone());
+                    self.levels[depth].section.nodes.push(node);
+                }
+                self.levels[depth].parsed = index + 1;
+            }
+
+            // Descend into a lone wrapper that may still be open.
+            let flags = &mut self.flags;
+            let level = &self.levels[depth];
+            if inner.is_none() && keep_last && children.len() == level.parsed + 1 {
+                let lone = children[..level.parsed].iter().all(|c| {
+                    c.value()
+                        .as_text()
+                        .is_some_and(|text| text.trim().is_empty())
+                });
+                let wrapper = children
+                    .last()
+                    .and_then(|c| scraper::ElementRef::wrap(*c))
+                    .filter(|e| lone && Self::is_wrapper(e, flags));
+                if let Some(wrapper) = wrapper {
+                    let inner = Self::enter(&wrapper, flags);
+                    self.levels.push(inner);
+                }
+            }
+            depth += 1;
+        }
+        if !keep_last {
+            while self.levels.len() > 1 {
+                self.finish_level(self.levels.len() - 1, html, &mut nodes);
+            }
+        }
+        nodes
+    }
+
+    /// Parse a finished child of the body or a wrapper.
+    fn parse_child(
+        child: &ego_tree::NodeRef<scraper::Node>,
+        flags: &mut ParseContext,
+    ) -> Option<Node> {
+        let mut node = match scraper::ElementRef::wrap(*child) {
+            Some(elem) => match RawNode::from_element_ref(&elem, flags) {
+                Ok(node) => node,
+                Err(e) => {
+                    tracing::debug!("Failed to parse child: {e:?}");
+                    return None;
+                }
+            },
+            None => RawNode::from_text_node(child.value().as_text()?, flags)?,
+        };
+        node.clean(&flags.clean);
+        node.sanitize(&flags.sanitize);
+        match node {
+            // Skipped elements, and those past the limits.
+            RawNode::Empty => None,
+            node => Some(Node::new(node)),
+        }
+    }
+
+    /// Check if an element only wraps the content of the page.
+    fn is_wrapper(elem: &scraper::ElementRef, flags: &ParseFlags) -> bool {
+        matches!(
+            elem.value().name.local.as_ref(),
+            "div" | "main" | "article" | "section"
+        ) && flags.semantic(elem).is_none()
+            && !flags.should_skip(elem)
+    }
+
+    /// Enter an element, to parse its children as they finish.
+    fn enter(elem: &scraper::ElementRef, flags: &mut ParseContext) -> Level {
+        let restore = (flags.lang.clone(), flags.dir, flags.parsing);
+        let name = elem.value().name.local.to_ascii_lowercase();
+        flags.remaining_depth = flags.remaining_depth.saturating_sub(1);
+        flags.path.push(name.to_string());
+        flags.report.elements += 1;
+        flags.report.nodes += 1;
+        flags.parsing |= flags.should_parse(elem);
+        let (lang, dir) = flags.inherit_language(elem);
+        let kind = elem
+            .attr("role")
+            .and_then(SectionKind::from_role)
+            .unwrap_or_else(|| SectionKind::from_element(&name));
+        let mut section = Section::new_set().with_kind(kind);
+        (section.lang, section.dir) = (lang, dir);
+        Level {
+            id: elem.id(),
+            parsed: 0,
+            section,
+            restore,
+        }
+    }
+
+    /// Parse the rest of a wrapper and the wrappers within it, and leave
+    /// them.
+    fn finish_level(&mut self, depth: usize, html: &scraper::Html, nodes: &mut Vec<Node>) {
+        while self.levels.len() > depth {
+            let level = self.levels.last_mut().expect("levels past depth");
+            if let Some(elem) = html.tree.get(level.id) {
+                for child in elem.children().skip(level.parsed) {
+                    if let Some(node) = Self::parse_child(&child, &mut self.flags) {
+                        nodes.push(node.clone());
+                        level.section.nodes.push(node);
+                    }
+                }
+            }
+            let level = self.levels.pop().expect("levels past depth");
+            let flags = &mut self.flags;
+            flags.remaining_depth = flags.remaining_depth.saturating_add(1);
+            flags.path.pop();
+            (flags.lang, flags.dir, flags.parsing) = level.restore;
+            if let Some(parent) = self.levels.last_mut() {
+                parent.section.nodes.push(Node::new(level.section.into()));
+                // The wrapper was the last child parsed.
+                parent.parsed += 1;
+            }
+        }
+    }
+}
+
+/// Tree sink of a streamed document, which shares the document so its
+/// finished elements can be parsed while the rest is still arriving.
+struct StreamSink(Rc<scraper::HtmlTreeSink>);
+
+impl TreeSink for StreamSink {
+    type Output = ();
+    type Handle = ego_tree::NodeId;
+    type ElemName<'a> = <scraper::HtmlTreeSink as TreeSink>::ElemName<'a>;
+
+    fn finish(self) {}
+
+    fn parse_error(&self, msg: Cow<'static, str>) {
+        self.0.parse_error(msg);
+    }
+
+    fn get_document(&self) -> Self::Handle {
+        self.0.get_document()
+    }
+
+    fn elem_name<'a>(&'a self, target: &'a Self::Handle) -> Self::ElemName<'a> {
+        self.0.elem_name(target)
+    }
+
+    fn create_element(
+        &self,
+        name: QualName,
+        attrs: Vec<Attribute>,
+        flags: ElementFlags,
+    ) -> Self::Handle {
+        self.0.create_element(name, attrs, flags)
+    }
+
+    fn create_comment(&self, text: StrTendril) -> Self::Handle {
+        self.0.create_comment(text)
+    }
+
+    fn create_pi(&self, target: StrTendril, data: StrTendril) -> Self::Handle {
+        self.0.create_pi(target, data)
+    }
+
+    fn append(&self, parent: &Self::Handle, child: NodeOrText<Self::Handle>) {
+        self.0.append(parent, child);
+    }
+
+    fn append_based_on_parent_node(
+        &self,
+        element: &Self::Handle,
+        prev_element: &Self::Handle,
+        child: NodeOrText<Self::Handle>,
+    ) {
+        self.0
+            .append_based_on_parent_node(element, prev_element, child);
+    }
+
+    fn append_doctype_to_document(
+        &self,
+        name: StrTendril,
+        public_id: StrTendril,
+        system_id: StrTendril,
+    ) {
+        self.0
+            .append_doctype_to_document(name, public_id, system_id);
+    }
+
+    fn mark_script_already_started(&self, node: &Self::Handle) {
+        self.0.mark_script_already_started(node);
+    }
+
+    fn pop(&self, node: &Self::Handle) {
+        self.0.pop(node);
+    }
+
+    fn get_template_contents(&self, target: &Self::Handle) -> Self::Handle {
+        self.0.get_template_contents(target)
+    }
+
+    fn same_node(&self, x: &Self::Handle, y: &Self::Handle) -> bool {
+        self.0.same_node(x, y)
+    }
+
+    fn set_quirks_mode(&self, mode: QuirksMode) {
+        self.0.set_quirks_mode(mode);
+    }
+
+    fn append_before_sibling(&self, sibling: &Self::Handle, new_node: NodeOrText<Self::Handle>) {
+        self.0.append_before_sibling(sibling, new_node);
+    }
+
+    fn add_attrs_if_missing(&self, target: &Self::Handle, attrs: Vec<Attribute>) {
+        self.0.add_attrs_if_missing(target, attrs);
+    }
+
+    fn associate_with_form(
+        &self,
+        target: &Self::Handle,
+        form: &Self::Handle,
+        nodes: (&Self::Handle, Option<&Self::Handle>),
+    ) {
+        self.0.associate_with_form(target, form, nodes);
+    }
+
+    fn remove_from_parent(&self, target: &Self::Handle) {
+        self.0.remove_from_parent(target);
+    }
+
+    fn reparent_children(&self, node: &Self::Handle, new_parent: &Self::Handle) {
+        self.0.reparent_children(node, new_parent);
+    }
+
+    fn is_mathml_annotation_xml_integration_point(&self, handle: &Self::Handle) -> bool {
+        self.0.is_mathml_annotation_xml_integration_point(handle)
+    }
+
+    fn set_current_line(&self, line_number: u64) {
+        self.0.set_current_line(line_number);
+    }
+
+    fn allow_declarative_shadow_roots(&self, intended_parent: &Self::Handle) -> bool {
+        self.0.allow_declarative_shadow_roots(intended_parent)
+    }
+
+    fn attach_declarative_shadow(
+        &self,
+        location: &Self::Handle,
+        template: &Self::Handle,
+        attrs: &[Attribute],
+    ) -> bool {
+        self.0.attach_declarative_shadow(location, template, attrs)
+    }
+}
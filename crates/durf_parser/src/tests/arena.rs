@@ -0,0 +1,66 @@
+//! Arena-backed AST tests.
+
+use super::*;
+
+const PAGE: &str = r#"
+    <html lang="ja"><body>
+        <main>
+            <h1>Title</h1>
+            <p>Some <b>bold</b> and <i>italic</i> text</p>
+            <details><summary>More</summary><p><ruby>雪<rt>ゆき</rt></ruby></p></details>
+            <form action="/search"><input name="q" value="snow"></form>
+            <pre><code class="language-rust">fn main() {}</code></pre>
+        </main>
+        <p>Footer</p>
+    </body></html>
+    "#;
+
+#[test_log::test]
+fn arena_round_trip() {
+    let ast = parse(PAGE, ParseFlags::default());
+    let arena = ast.to_arena().unwrap();
+    assert_eq!(arena.len(), ast.root.node_count());
+    assert_eq!(arena.lang.as_deref(), Some("ja"));
+
+    let copy = arena.to_ast();
+    assert_eq!(copy.to_string(), ast.to_string());
+    assert_eq!(copy.lang, ast.lang);
+}
+
+#[test_log::test]
+fn arena_links() {
+    let ast = parse(PAGE, ParseFlags::default());
+    let arena = ast.to_arena().unwrap();
+    let root = arena.root();
+    assert_eq!(arena.parent(root), None);
+
+    // Every node is visited once, and is a child of its parent.
+    let ids: Vec<NodeId> = arena.descendants(root).collect();
+    assert_eq!(ids.len(), arena.len());
+    for id in &ids[1..] {
+        let parent = arena.parent(*id).unwrap();
+        assert!(arena.children(parent).any(|child| child == *id));
+        assert_eq!(arena.ancestors(*id).last(), Some(root));
+    }
+
+    // Text is stored once, with equal attributes shared.
+    let texts: Vec<String> = ids
+        .iter()
+        .filter_map(|id| match &arena.node(*id).data {
+            ArenaData::Text(text) => Some(arena.collect(text)),
+            _ => None,
+        })
+        .collect();
+    assert!(texts.contains(&"Some bold and italic text".to_string()));
+    let attributes: Vec<*const TextAttributes> = ids
+        .iter()
+        .filter_map(|id| match &arena.node(*id).data {
+            ArenaData::Text(text) => Some(arena.fragments(text).collect::<Vec<_>>()),
+            _ => None,
+        })
+        .flatten()
+        .map(|(_, attributes)| attributes as *const TextAttributes)
+        .collect();
+    let distinct: std::collections::HashSet<_> = attributes.iter().collect();
+    assert!(distinct.len() < attributes.len());
+}
@@ -0,0 +1,90 @@
+//! AST builder and macro tests.
+
+use super::*;
+
+fn built() -> Ast {
+    AstBuilder::new()
+        .heading(1, "History")
+        .para(|p| {
+            p.text("Visited ")
+                .bold("durf")
+                .text(" at ")
+                .link("example", "https://example.com")
+        })
+        .list(|l| l.text("One").para(|p| p.italic("Two")))
+        .enumeration(|l| l.text("First"))
+        .section("More", |s| s.code(Some("rust"), "fn main() {}"))
+        .details("Hidden", |d| d.text("Shown when open"))
+        .build()
+}
+
+#[test_log::test]
+fn build_ast() {
+    let ast = built();
+    let root = as_section(&ast.root);
+    assert_eq!(root.nodes.len(), 6);
+    let para = as_text(&root.nodes[1]);
+    assert_eq!(para.collect(), "Visited durf at example");
+    assert!(para.fragments[1].attributes.bold);
+    assert_eq!(
+        para.fragments[3].attributes.link.as_deref(),
+        Some("https://example.com")
+    );
+    let list = as_section(&root.nodes[2]);
+    assert_eq!(list.ordering, SectionOrdering::List);
+    let section = as_section(&root.nodes[4]);
+    assert_eq!(
+        section.title.as_ref().map(Text::collect).as_deref(),
+        Some("More")
+    );
+    assert!(matches!(&*root.nodes[5], RawNode::Details(d) if d.nodes.len() == 1));
+
+    let ast = AstBuilder::new().lang("ja").dir(Direction::Ltr).build();
+    assert_eq!(ast.lang.as_deref(), Some("ja"));
+    assert_eq!(ast.dir, Some(Direction::Ltr));
+}
+
+#[test_log::test]
+fn build_matches_parse() {
+    let built = AstBuilder::new()
+        .heading(2, "Title")
+        .para(|p| p.text("Some ").bold("bold").text(" text"))
+        .build();
+    let mut parsed = parse(
+        "<h2>Title</h2><p>Some <b>bold</b> text</p>",
+        ParseFlags::default(),
+    );
+    parsed.minimize();
+    assert_eq!(built.to_string(), parsed.to_string());
+}
+
+#[test_log::test]
+fn macro_matches_builder() {
+    let url = "https://example.com";
+    let more = String::from("More");
+    let ast = durf! {
+        h1 "History";
+        p { "Visited " b "durf" " at " a(url) "example" };
+        ul { "One"; p { i "Two" }; };
+        ol { "First"; };
+        section (more) { pre("rust") "fn main() {}"; };
+        details "Hidden" { "Shown when open"; };
+    };
+    assert_eq!(ast.to_string(), built().to_string());
+}
+
+#[test_log::test]
+fn build_sanitizes_text() {
+    let ast = AstBuilder::new()
+        .text("abc\u{202E}fed")
+        .section("Ti\u{0007}tle", |s| s.text("\u{2066}nested"))
+        .build();
+    let export = ast.to_string();
+    assert!(
+        !export
+            .chars()
+            .any(|c| is_bidi_control(c) || c == '\u{0007}')
+    );
+    assert!(export.contains("abcfed"));
+    assert!(export.contains("nested"));
+}
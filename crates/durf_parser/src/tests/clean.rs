@@ -0,0 +1,71 @@
+//! Text cleaning tests.
+
+use super::*;
+
+const PAGE: &str = "<p>\u{3000}そう、無敵だと信じていた〝王宮城塞〟</p>\
+    <p>zero\u{200B}width soft\u{AD}hyphen</p>\
+    <p>👩\u{200D}💻 می\u{200C}خواهم</p>\
+    <p>“Smart” ‘quotes’…</p>\
+    <p>ｶﾀｶﾅ ＡＢＣ e\u{301}</p>\
+    <pre>keep\u{3000}“code”\u{200B}</pre>";
+
+#[test_log::test]
+fn clean_defaults() {
+    let export = parse(PAGE, ParseFlags::default()).to_string();
+    // Indentation of Japanese prose is kept.
+    assert!(export.contains("\u{3000}そう"));
+    assert!(export.contains("zerowidth softhyphen"));
+    // Joiners shape emoji and scripts, so they are kept.
+    assert!(export.contains("👩\u{200D}💻 می\u{200C}خواهم"));
+    assert!(export.contains("“Smart” ‘quotes’…"));
+    assert!(export.contains("ｶﾀｶﾅ ＡＢＣ e\u{301}"));
+    assert!(export.contains("keep\u{3000}“code”\u{200B}"));
+}
+
+#[test_log::test]
+fn clean_all() {
+    let flags = ParseFlags {
+        clean: CleanFlags {
+            normalization: Some(NormalizationForm::Nfkc),
+            fullwidth_space: SpaceMode::Remove,
+            remove_zero_width: true,
+            remove_joiners: true,
+            remove_soft_hyphens: true,
+            ascii_punctuation: true,
+        },
+        ..Default::default()
+    };
+    let export = parse(PAGE, flags).to_string();
+    assert!(export.contains("Text: そう"));
+    assert!(export.contains("👩💻 میخواهم"));
+    assert!(export.contains("\"Smart\" 'quotes'..."));
+    assert!(export.contains("カタカナ ABC \u{e9}"));
+    assert!(export.contains("keep\u{3000}“code”\u{200B}"));
+
+    let flags = CleanFlags {
+        fullwidth_space: SpaceMode::Ascii,
+        remove_zero_width: false,
+        remove_soft_hyphens: false,
+        ..Default::default()
+    };
+    assert!(!flags.is_noop());
+    assert_eq!(
+        flags.clean("a\u{3000}b\u{200B}c\u{AD}"),
+        "a b\u{200B}c\u{AD}"
+    );
+    let flags = CleanFlags {
+        normalization: Some(NormalizationForm::Nfd),
+        ..flags
+    };
+    assert_eq!(flags.clean("\u{e9}"), "e\u{301}");
+}
+
+#[test_log::test]
+fn clean_flags_deserialize() {
+    use serde::de::{IntoDeserializer, value::Error};
+
+    let form = NormalizationForm::deserialize(IntoDeserializer::<Error>::into_deserializer("nfkc"));
+    assert_eq!(form.unwrap(), NormalizationForm::Nfkc);
+    let mode = SpaceMode::deserialize(IntoDeserializer::<Error>::into_deserializer("ascii"));
+    assert_eq!(mode.unwrap(), SpaceMode::Ascii);
+}
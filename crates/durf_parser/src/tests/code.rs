@@ -0,0 +1,57 @@
+//! Code block tests.
+
+use super::*;
+
+#[test_log::test]
+fn parse_code_block() {
+    let page = "<html><body>\
+        <p>Use <code>main</code> to start.</p>\
+        <pre><code class=\"hljs language-rust\">fn main() {\n    println!(\"hi\");\n}\n</code></pre>\
+        </body></html>";
+    let mut ast = parse(page, ParseFlags::default());
+    ast.minimize();
+    tracing::trace!("{ast}");
+
+    let code = find_code(&ast.root).unwrap();
+    assert_eq!(code.language(), Some("rust"));
+    assert_eq!(
+        code.text.collect(),
+        "fn main() {\n    println!(\"hi\");\n}\n"
+    );
+
+    // Inline code stays an attribute.
+    let root = as_section(&ast.root);
+    let text = as_text(&root.nodes[0]);
+    assert!(text.fragments.iter().any(|f| f.attributes.preformatted));
+}
+
+#[test_log::test]
+fn code_language_sources() {
+    let cases = [
+        (r#"<pre data-lang="Python">x = 1</pre>"#, Some("python")),
+        (r#"<pre class="lang-sh">ls</pre>"#, Some("sh")),
+        (r#"<pre><code>plain</code></pre>"#, None),
+    ];
+    for (page, language) in cases {
+        let ast = parse(page, ParseFlags::default());
+        let code = find_code(&ast.root).unwrap();
+        assert_eq!(code.language(), language, "{page}");
+    }
+}
+
+#[test_log::test]
+fn code_keeps_line_breaks() {
+    let page = "<pre>a<br>b\n\tc</pre>";
+    let ast = parse(page, ParseFlags::default());
+    let code = find_code(&ast.root).unwrap();
+    assert_eq!(code.text.collect(), "a\nb\n\tc");
+}
+
+#[test_log::test]
+fn code_skips_hidden_content() {
+    let page = r#"<pre>shown<script>run()</script><span hidden>hidden</span><span aria-hidden="true"><b>aria</b></span>
+text</pre>"#;
+    let ast = parse(page, ParseFlags::default());
+    let code = find_code(&ast.root).unwrap();
+    assert_eq!(code.text.collect(), "shown\ntext");
+}
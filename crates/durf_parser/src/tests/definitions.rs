@@ -0,0 +1,53 @@
+//! Definition list tests.
+
+use super::*;
+
+#[test_log::test]
+fn parse_definition_list() {
+    let page = r#"
+        <dl>
+            <dt>-v</dt>
+            <dt>--verbose</dt>
+            <dd>Print more output.</dd>
+            <dt>-q</dt>
+            <dd>Print less output.</dd>
+            <dd>Can be repeated.</dd>
+            <div>
+                <dt>-h</dt>
+                <dd>Print help.</dd>
+            </div>
+        </dl>
+        "#;
+    let mut ast = parse(page, ParseFlags::default());
+    ast.minimize();
+    tracing::trace!("{ast}");
+
+    let list = as_section(&ast.root);
+    assert_eq!(list.ordering, SectionOrdering::Definition);
+
+    let definitions: Vec<(String, usize)> = list
+        .definitions()
+        .map(|(term, descriptions)| (term.unwrap().collect(), descriptions.len()))
+        .collect();
+    assert_eq!(
+        definitions,
+        vec![
+            ("-v, --verbose".to_string(), 1),
+            ("-q".to_string(), 2),
+            ("-h".to_string(), 1),
+        ]
+    );
+}
+
+#[test_log::test]
+fn definition_without_term() {
+    let page = r#"<dl><dd>Orphan</dd></dl>"#;
+    let mut ast = parse(page, ParseFlags::default());
+    ast.minimize();
+
+    let list = as_section(&ast.root);
+    let definitions: Vec<_> = list.definitions().collect();
+    assert_eq!(definitions.len(), 1);
+    assert!(definitions[0].0.is_none());
+    assert_eq!(definitions[0].1.len(), 1);
+}
@@ -0,0 +1,178 @@
+//! Depth policy tests.
+
+use super::*;
+
+const PAGE: &str = r#"
+    <html><body><div><div>
+        <p>Intro</p>
+        <div><div class="article">
+            <h2>Deep <i>title</i></h2>
+            <p>Deep <b>bold</b> and <a href="/more">linked</a> text</p>
+            <div><p>Deeper</p><script>hidden()</script><span hidden>Hidden</span></div>
+        </div></div>
+    </div></div></body></html>
+    "#;
+
+#[test_log::test]
+fn depth_policy_error() {
+    let ast = parse(
+        PAGE,
+        ParseFlags {
+            remaining_depth: 5,
+            depth_policy: DepthPolicy::Error,
+            ..Default::default()
+        },
+    );
+    let export = ast.to_string();
+    assert!(export.contains("Intro"));
+    assert!(!export.contains("Deep"));
+    assert_eq!(ast.report.depth_hits.len(), 1);
+}
+
+#[test_log::test]
+fn depth_policy_truncate() {
+    let ast = parse(
+        PAGE,
+        ParseFlags {
+            remaining_depth: 5,
+            depth_policy: DepthPolicy::Truncate,
+            ..Default::default()
+        },
+    );
+    let export = ast.to_string();
+    tracing::trace!("{export}");
+    assert!(export.contains("Intro"));
+    assert!(export.contains("[…]"));
+    assert!(!export.contains("Deep"));
+    assert_eq!(
+        ast.report.depth_hits,
+        vec!["html > body > div > div > div > div"]
+    );
+}
+
+#[test_log::test]
+fn depth_policy_flatten() {
+    let mut ast = parse(
+        PAGE,
+        ParseFlags {
+            remaining_depth: 5,
+            ..Default::default()
+        },
+    );
+    assert!(ast.report.depth_hits.is_empty());
+    assert_eq!(
+        ast.report.fallbacks,
+        vec![ParseFallback {
+            path: "html > body > div > div > div > div".into(),
+            kind: FallbackKind::Flattened,
+        }]
+    );
+    assert_eq!(ast.report.not_rendered(), 0);
+
+    ast.minimize();
+    let export = ast.to_string();
+    tracing::trace!("{export}");
+    assert!(!export.contains("hidden()"));
+    assert!(!export.contains("Hidden"));
+
+    // Each block becomes a text node, keeping inline formatting.
+    let root = as_section(&ast.root);
+    let flattened = as_section(&root.nodes[1]);
+    let texts: Vec<&Text> = flattened
+        .nodes
+        .iter()
+        .filter_map(|n| match &**n {
+            RawNode::Text(t) => Some(t),
+            _ => None,
+        })
+        .collect();
+    let collected: Vec<String> = texts
+        .iter()
+        .map(|t| t.collect().trim().to_string())
+        .collect();
+    assert!(collected.ends_with(&[
+        "Deep title".to_string(),
+        "Deep bold and linked text".to_string(),
+        "Deeper".to_string(),
+    ]));
+
+    let title = texts[texts.len() - 3];
+    assert_eq!(title.heading_level(), Some(2));
+    assert!(
+        title
+            .fragments
+            .iter()
+            .any(|f| f.text == "title" && f.attributes.italic)
+    );
+    let body = texts[texts.len() - 2];
+    assert!(
+        body.fragments
+            .iter()
+            .any(|f| f.text == "bold" && f.attributes.bold)
+    );
+    assert!(
+        body.fragments
+            .iter()
+            .any(|f| f.text == "linked" && f.attributes.link.as_deref() == Some("/more"))
+    );
+}
+
+#[test_log::test]
+fn flatten_inside_text() {
+    let page = "<p>a <b>b <i>c <u>d</u></i></b></p>";
+    let flags = ParseFlags {
+        remaining_depth: 5,
+        ..Default::default()
+    };
+    let mut ast = parse(page, flags);
+    ast.minimize();
+    let text = as_text(&ast.root);
+    assert_eq!(text.collect(), "a b c d");
+    let d = text.fragments.last().unwrap();
+    assert!(d.attributes.bold && d.attributes.italic);
+    assert_eq!(
+        ast.report.fallbacks,
+        vec![ParseFallback {
+            path: "html > body > p > b > i > u".into(),
+            kind: FallbackKind::Flattened,
+        }]
+    );
+}
+
+#[test_log::test]
+fn flatten_noscript_and_ruby() {
+    let content = r#"<noscript><p>Enable <b>JavaScript</b></p></noscript>
+        <p><ruby>日本<rp>(</rp><rt>にほん</rt><rp>)</rp></ruby>の雪</p>"#;
+    let page = format!("<div><div><div>{content}</div></div></div>");
+    let flags = ParseFlags {
+        remaining_depth: 4,
+        ..Default::default()
+    };
+    let mut ast = parse(&page, flags);
+    assert!(
+        ast.report
+            .fallbacks
+            .iter()
+            .any(|f| f.kind == FallbackKind::Flattened)
+    );
+    ast.minimize();
+    let export = ast.to_string();
+    assert!(export.contains("Enable **JavaScript**"), "{export}");
+    assert!(!export.contains("<p>"));
+    assert!(export.contains("日本(にほん)の雪"), "{export}");
+
+    // Flattened text matches the text of a normal parse.
+    let mut full = parse(&format!("<div>{content}</div>"), ParseFlags::default());
+    full.minimize();
+    let flattened: Vec<String> = texts(&ast.root).iter().map(Text::collect).collect();
+    let parsed: Vec<String> = texts(&full.root).iter().map(Text::collect).collect();
+    assert_eq!(flattened, parsed);
+    let annotation = |ast: &Ast| {
+        texts(&ast.root)
+            .into_iter()
+            .flat_map(|t| t.fragments)
+            .find_map(|f| f.attributes.annotation)
+    };
+    assert_eq!(annotation(&ast).as_deref(), Some("にほん"));
+    assert_eq!(annotation(&ast), annotation(&full));
+}
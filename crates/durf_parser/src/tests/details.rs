@@ -0,0 +1,39 @@
+//! Details tests.
+
+use super::*;
+
+#[test_log::test]
+fn parse_details() {
+    let page = r#"
+        <html><body>
+            <details>
+                <summary>What is <b>durf</b>?</summary>
+                <p>A dumb document surfer.</p>
+            </details>
+            <details open>
+                <p>No summary here.</p>
+            </details>
+        </body></html>
+        "#;
+    let mut ast = parse(page, ParseFlags::default());
+    ast.minimize();
+    tracing::trace!("{ast}");
+
+    let root = as_section(&ast.root);
+    let details: Vec<&Details> = root
+        .nodes
+        .iter()
+        .filter_map(|n| match &**n {
+            RawNode::Details(d) => Some(d),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(details.len(), 2);
+
+    assert_eq!(details[0].summary.collect().trim(), "What is durf?");
+    assert!(!details[0].open);
+    assert_eq!(details[0].nodes.len(), 1);
+
+    assert_eq!(details[1].summary.collect(), "Details");
+    assert!(details[1].open);
+}
@@ -0,0 +1,172 @@
+//! AST diff tests.
+
+use super::*;
+
+fn page(paragraph: &str) -> Ast {
+    AstBuilder::new()
+        .heading(1, "Docs")
+        .text(paragraph)
+        .section("Install", |s| {
+            s.text("Run the installer").code(None, "make")
+        })
+        .text("Footer")
+        .build()
+}
+
+#[test_log::test]
+fn diff_unchanged() {
+    let ast = page("Hello world");
+    assert!(Ast::diff(&ast, &ast.clone()).is_empty());
+    assert!(Ast::diff(&ast, &page("Hello world")).is_empty());
+}
+
+#[test_log::test]
+fn diff_changed_text() {
+    let diff = Ast::diff(&page("Hello world"), &page("Hello there world"));
+    assert!(diff.inserted.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.changed.len(), 1);
+    let change = &diff.changed[0];
+    assert_eq!(change.old_path, NodePath(vec![1]));
+    assert_eq!(change.new_path, NodePath(vec![1]));
+    let edits: Vec<_> = change
+        .text
+        .iter()
+        .map(|e| (e.kind, e.text.as_str()))
+        .collect();
+    assert_eq!(
+        edits,
+        [
+            (EditKind::Equal, "Hello "),
+            (EditKind::Inserted, "there "),
+            (EditKind::Equal, "world"),
+        ]
+    );
+}
+
+#[test_log::test]
+fn diff_inserted_and_removed() {
+    let old = page("Hello world");
+    let new = AstBuilder::new()
+        .heading(1, "Docs")
+        .text("Hello world")
+        .code(Some("sh"), "curl example.com")
+        .section("Install", |s| s.text("Run the installer"))
+        .text("Footer")
+        .build();
+    let diff = Ast::diff(&old, &new);
+    assert!(diff.changed.is_empty());
+    assert_eq!(diff.inserted.len(), 1);
+    assert_eq!(diff.inserted[0].0.to_string(), "/2");
+    assert!(matches!(&*diff.inserted[0].1, RawNode::Code(_)));
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].0, NodePath(vec![2, 1]));
+    assert!(
+        old.root
+            .node_at(&diff.removed[0].0)
+            .is_some_and(|n| n.children().is_empty())
+    );
+}
+
+#[test_log::test]
+fn diff_section_title() {
+    let old = page("Hello world");
+    let new = AstBuilder::new()
+        .heading(1, "Docs")
+        .text("Hello world")
+        .section("Installing", |s| {
+            s.text("Run the installer").code(None, "make")
+        })
+        .text("Footer")
+        .build();
+    let diff = Ast::diff(&old, &new);
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].new_path, NodePath(vec![2]));
+    assert_eq!(
+        diff.changed[0].text,
+        [
+            TextEdit {
+                kind: EditKind::Removed,
+                text: "Install".into(),
+            },
+            TextEdit {
+                kind: EditKind::Inserted,
+                text: "Installing".into(),
+            },
+        ]
+    );
+}
+
+#[test_log::test]
+fn diff_text_by_character() {
+    let diff = Ast::diff(&page("雪が降る"), &page("雨が降る"));
+    let edits: Vec<_> = diff.changed[0]
+        .text
+        .iter()
+        .map(|e| (e.kind, e.text.as_str()))
+        .collect();
+    assert_eq!(
+        edits,
+        [
+            (EditKind::Removed, "雪"),
+            (EditKind::Inserted, "雨"),
+            (EditKind::Equal, "が降る"),
+        ]
+    );
+}
+
+/// Rebuild the old and new text from edits.
+fn sides(edits: &[TextEdit]) -> (String, String) {
+    let (mut old, mut new) = (String::new(), String::new());
+    for edit in edits {
+        if edit.kind != EditKind::Inserted {
+            old.push_str(&edit.text);
+        }
+        if edit.kind != EditKind::Removed {
+            new.push_str(&edit.text);
+        }
+    }
+    (old, new)
+}
+
+#[test_log::test]
+fn diff_long_text() {
+    // Every third character changes, with the ends differing too.
+    let old: String = (0..3000).map(|i| ['雪', 'が', '降'][i % 3]).collect();
+    let new: String = (0..3000).map(|i| ['雨', 'が', '降'][i % 3]).collect();
+    let diff = Ast::diff(&page(&old), &page(&new));
+    let edits = &diff.changed[0].text;
+    assert_eq!(sides(edits), (old, new));
+    let equal: usize = edits
+        .iter()
+        .filter(|e| e.kind == EditKind::Equal)
+        .map(|e| e.text.chars().count())
+        .sum();
+    assert_eq!(equal, 2000);
+}
+
+#[test_log::test]
+fn diff_huge_code() {
+    let old: String = (0..32_000)
+        .map(|i| char::from_u32(0x3041 + i % 80).unwrap())
+        .collect();
+    let new: String = old.chars().rev().collect();
+    let old_ast = AstBuilder::new().code(None, old.clone()).build();
+    let new_ast = AstBuilder::new().code(None, new.clone()).build();
+    let diff = Ast::diff(&old_ast, &new_ast);
+    assert_eq!(diff.changed.len(), 1);
+    // Too large to align, so the whole text is replaced.
+    assert_eq!(
+        diff.changed[0].text,
+        [
+            TextEdit {
+                kind: EditKind::Removed,
+                text: old,
+            },
+            TextEdit {
+                kind: EditKind::Inserted,
+                text: new,
+            },
+        ]
+    );
+}
@@ -0,0 +1,114 @@
+//! Character encoding detection tests.
+
+use super::*;
+
+const TEXT: &str = "日本海側などでまた雪";
+
+/// Encode a page with a head and a paragraph of text.
+fn page(head: &str, encoding: &'static encoding_rs::Encoding) -> Vec<u8> {
+    let page = format!("<html><head>{head}</head><body><p>{TEXT}</p></body></html>");
+    encoding.encode(&page).0.into_owned()
+}
+
+fn assert_decoded(ast: &Ast, name: &str, source: EncodingSource) {
+    tracing::trace!("{ast}");
+    assert!(ast.to_string().contains(TEXT));
+    let encoding = ast.report.encoding.as_ref().unwrap();
+    assert_eq!(encoding.name, name);
+    assert_eq!(encoding.source, source);
+    assert!(!encoding.had_errors);
+}
+
+#[test_log::test]
+fn encoding_from_meta() {
+    let bytes = page(
+        "<!-- <meta charset=utf-8> --><meta charset='Shift_JIS'>",
+        encoding_rs::SHIFT_JIS,
+    );
+    let ast = Ast::from_html_bytes(&bytes, None, ParseFlags::default()).unwrap();
+    assert_decoded(&ast, "Shift_JIS", EncodingSource::Meta);
+
+    let bytes = page(
+        r#"<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=euc-jp">"#,
+        encoding_rs::EUC_JP,
+    );
+    let ast = Ast::from_html_bytes(&bytes, None, ParseFlags::default()).unwrap();
+    assert_decoded(&ast, "EUC-JP", EncodingSource::Meta);
+}
+
+#[test_log::test]
+fn encoding_priority() {
+    // The content type overrides the meta declaration.
+    let bytes = page("<meta charset=utf-8>", encoding_rs::EUC_JP);
+    let ast = Ast::from_html_bytes(
+        &bytes,
+        Some("text/html; charset=\"EUC-JP\""),
+        ParseFlags::default(),
+    )
+    .unwrap();
+    assert_decoded(&ast, "EUC-JP", EncodingSource::ContentType);
+
+    // The BOM overrides both.
+    let mut bytes = b"\xEF\xBB\xBF".to_vec();
+    bytes.extend(page("<meta charset=shift_jis>", encoding_rs::UTF_8));
+    let ast = Ast::from_html_bytes(
+        &bytes,
+        Some("text/html; charset=euc-jp"),
+        ParseFlags::default(),
+    )
+    .unwrap();
+    assert_decoded(&ast, "UTF-8", EncodingSource::Bom);
+}
+
+#[test_log::test]
+fn encoding_default() {
+    let ast =
+        Ast::from_html_bytes(&page("", encoding_rs::UTF_8), None, ParseFlags::default()).unwrap();
+    assert_decoded(&ast, "UTF-8", EncodingSource::Default);
+
+    let ast = Ast::from_html_bytes(b"<p>caf\xE9</p>", None, ParseFlags::default()).unwrap();
+    assert!(ast.to_string().contains("café"));
+    let encoding = ast.report.encoding.as_ref().unwrap();
+    assert_eq!(encoding.name, "windows-1252");
+    assert_eq!(encoding.source, EncodingSource::Default);
+}
+
+#[test_log::test]
+fn encoding_default_truncated() {
+    // Cut off within the last character, by the download or the input limit.
+    let page = "<p>日本語</p>".as_bytes();
+    let ast = Ast::from_html_bytes(&page[..page.len() - 5], None, ParseFlags::default()).unwrap();
+    assert!(ast.to_string().contains("日本"));
+    assert_eq!(ast.report.encoding.as_ref().unwrap().name, "UTF-8");
+
+    let flags = ParseFlags {
+        limits: ParseLimits {
+            max_input_bytes: 7,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let ast = Ast::from_html_bytes(page, None, flags).unwrap();
+    assert!(ast.to_string().contains("日"));
+    assert_eq!(ast.report.encoding.as_ref().unwrap().name, "UTF-8");
+}
+
+#[test_log::test]
+fn encoding_errors() {
+    let ast = Ast::from_html_bytes(
+        b"<p>bad \xFF\xFE utf-8</p>",
+        Some("text/html; charset=utf-8"),
+        ParseFlags::default(),
+    )
+    .unwrap();
+    assert!(ast.to_string().contains("bad \u{FFFD}\u{FFFD} utf-8"));
+    assert!(ast.report.encoding.as_ref().unwrap().had_errors);
+
+    let error = Ast::from_html_bytes(
+        b"<p>text</p>",
+        Some("text/html; charset=iso-2022-kr"),
+        ParseFlags::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(error, Error::Encoding { ref encoding, .. } if encoding == "iso-2022-kr"));
+}
@@ -0,0 +1,98 @@
+//! Error tests.
+
+use super::*;
+use std::error::Error as _;
+
+#[test_log::test]
+fn invalid_rules() {
+    for rule in [
+        ParseRule::from_element(""),
+        ParseRule::from_element("DIV"),
+        ParseRule::from_element("div.a"),
+        ParseRule::from_class(""),
+        ParseRule::from_class("a b"),
+    ] {
+        let flags = ParseFlags {
+            skip: vec![rule.clone()],
+            ..Default::default()
+        };
+        let err = Ast::from_html("<p>Text</p>", flags).unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidRule { .. }),
+            "{rule:?} is valid"
+        );
+    }
+
+    let flags = ParseFlags::default().with_semantic(ParseRule::from_class("c d"), Semantic::Code);
+    let err = Ast::from_html("", flags).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"invalid rule Class("c d"): class names cannot contain whitespace"#
+    );
+
+    assert!(ParseRule::from_element("my-element").validate().is_ok());
+    assert!(ParseRule::from_class("colorL").validate().is_ok());
+}
+
+#[test_log::test]
+fn unsupported_formats() {
+    let ast = Ast::from_content_type(
+        "<p>Hi</p>",
+        "text/html; charset=utf-8",
+        ParseFlags::default(),
+    );
+    assert!(ast.unwrap().to_string().contains("Hi"));
+    let ast = Ast::from_content_type("Hi", "TEXT/PLAIN", ParseFlags::default());
+    assert!(ast.unwrap().to_string().contains("Hi"));
+
+    let err = Ast::from_content_type("{}", "application/json", ParseFlags::default()).unwrap_err();
+    assert!(matches!(&err, Error::UnsupportedFormat { format } if format == "application/json"));
+    assert_eq!(err.to_string(), "unsupported format `application/json`");
+}
+
+#[test_log::test]
+fn depth_error_context() {
+    let flags = ParseFlags {
+        remaining_depth: 0,
+        depth_policy: DepthPolicy::Error,
+        ..Default::default()
+    };
+    let err = Ast::from_html("<p>Text</p>", flags).unwrap_err();
+    assert_eq!(err.to_string(), "failed to parse document root");
+
+    // The cause is chained.
+    let source = err.source().expect("Expected source");
+    assert_eq!(source.to_string(), "depth exceeded at <html> (html)");
+    let Error::DepthExceeded { element, path } = err.root_cause() else {
+        panic!("Expected depth error");
+    };
+    assert_eq!(element, "html");
+    assert_eq!(path, "html");
+}
+
+#[test_log::test]
+fn error_display() {
+    let err = Error::Encoding {
+        encoding: "Shift_JIS".into(),
+        offset: Some(12),
+    };
+    assert_eq!(
+        err.to_string(),
+        "failed to decode input as Shift_JIS at byte 12"
+    );
+    let err = Error::Encoding {
+        encoding: "UTF-8".into(),
+        offset: None,
+    };
+    assert_eq!(err.to_string(), "failed to decode input as UTF-8");
+    let err = Error::UnsupportedElement {
+        element: "video".into(),
+        path: "html > body > video".into(),
+    }
+    .context("failed to parse child");
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "unsupported element <video> (html > body > video)"
+    );
+    assert!(err.root_cause().source().is_none());
+}
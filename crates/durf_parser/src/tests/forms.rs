@@ -0,0 +1,94 @@
+//! Form tests.
+
+use super::*;
+
+#[test_log::test]
+fn parse_search_form() {
+    let page = r#"
+        <html><body>
+            <form action="/search" method="post" enctype="multipart/form-data">
+                <label for="q">Query</label>
+                <input id="q" type="search" name="q" value="durf">
+                <input type="hidden" name="lang" value="en">
+                <label><input type="checkbox" name="exact" checked> Exact</label>
+                <input type="radio" name="sort" value="new">
+                <input type="radio" name="sort" value="top" checked>
+                <select name="site">
+                    <option value="all">All</option>
+                    <option value="docs" selected>Docs</option>
+                </select>
+                <textarea name="notes">Some notes</textarea>
+                <input type="text" name="off" value="x" disabled>
+                <button>Go</button>
+            </form>
+        </body></html>
+        "#;
+    let mut ast = parse(page, ParseFlags::default());
+    ast.minimize();
+    tracing::trace!("{ast}");
+
+    let form = find_form(&ast.root).unwrap();
+    assert_eq!(form.action.as_deref(), Some("/search"));
+    assert_eq!(form.method, FormMethod::Post);
+    assert_eq!(form.enctype, "multipart/form-data");
+
+    let fields = form.fields();
+    assert_eq!(fields.len(), 9);
+    assert_eq!(fields[0].kind, FieldKind::Search);
+    assert_eq!(fields[0].label.as_deref(), Some("Query"));
+    assert_eq!(fields[2].kind, FieldKind::Checkbox);
+    assert_eq!(fields[2].label.as_deref(), Some("Exact"));
+    assert_eq!(fields[8].kind, FieldKind::Submit);
+    assert_eq!(fields[8].label.as_deref(), Some("Go"));
+
+    let data = form.data();
+    let expected: Vec<(String, String)> = [
+        ("q", "durf"),
+        ("lang", "en"),
+        ("exact", "on"),
+        ("sort", "top"),
+        ("site", "docs"),
+        ("notes", "Some notes"),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect();
+    assert_eq!(data, expected);
+}
+
+#[test_log::test]
+fn form_defaults() {
+    let page = r#"<form><select name="s"><option>A</option><option>B</option></select></form>"#;
+    let ast = parse(page, ParseFlags::default());
+
+    let form = find_form(&ast.root).unwrap();
+    assert_eq!(form.action, None);
+    assert_eq!(form.method, FormMethod::Get);
+    assert_eq!(form.enctype, Form::DEFAULT_ENCTYPE);
+    assert_eq!(form.data(), vec![("s".to_string(), "A".to_string())]);
+}
+
+#[test_log::test]
+fn fields_in_text() {
+    let page = r#"
+        <form>
+            <p><label for="q">Query</label> <input id="q" name="q"></p>
+            <p>Sort by <select name="sort"><option>New</option></select> first</p>
+        </form>
+        "#;
+    let mut ast = parse(page, ParseFlags::default());
+    ast.minimize();
+    tracing::trace!("{ast}");
+
+    let form = find_form(&ast.root).unwrap();
+    let fields = form.fields();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0].name.as_deref(), Some("q"));
+    assert_eq!(fields[0].label.as_deref(), Some("Query"));
+    assert!(matches!(fields[1].kind, FieldKind::Select(_)));
+
+    // The label is shown by its field, and text around a field is kept.
+    let export = ast.to_string();
+    assert_eq!(export.matches("Query").count(), 1, "{export}");
+    assert!(export.find("Sort by") < export.find("first"));
+}
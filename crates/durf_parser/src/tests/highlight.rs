@@ -0,0 +1,182 @@
+//! Syntax highlighting tests.
+
+use super::*;
+
+/// Highlight code, returning classified tokens.
+fn tokens(code: &str, language: &str) -> Vec<(String, TokenClass)> {
+    let mut code = Code::new(code, Some(language.into()));
+    assert!(code.highlight(), "{language} is not supported");
+    code.text
+        .fragments
+        .iter()
+        .filter_map(|f| Some((f.text.clone(), f.attributes.token?)))
+        .collect()
+}
+
+/// Check that a token has a class.
+fn has(tokens: &[(String, TokenClass)], text: &str, class: TokenClass) -> bool {
+    tokens.iter().any(|(t, c)| t == text && *c == class)
+}
+
+#[test_log::test]
+fn highlight_keeps_text() {
+    let source = "fn main() {\n    // Hi.\n    let s = \"a\\\"b\";\n}\n";
+    let mut code = Code::new(source, Some("rust".into()));
+    assert!(code.highlight());
+    assert_eq!(code.text.collect(), source);
+    assert!(
+        code.text
+            .fragments
+            .iter()
+            .all(|f| f.attributes.preformatted)
+    );
+}
+
+#[test_log::test]
+fn highlight_rust() {
+    let t = tokens(
+        "fn main<'a>(x: &'a str) -> u8 {\n    println!(\"{x}\"); // Done.\n    let c = 'c';\n    Some(1)\n}",
+        "rs",
+    );
+    assert!(has(&t, "fn", TokenClass::Keyword));
+    assert!(has(&t, "main", TokenClass::Function));
+    assert!(has(&t, "'a", TokenClass::Type));
+    assert!(has(&t, "u8", TokenClass::Type));
+    assert!(has(&t, "println!", TokenClass::Function));
+    assert!(has(&t, "\"{x}\"", TokenClass::String));
+    assert!(has(&t, "// Done.", TokenClass::Comment));
+    assert!(has(&t, "'c'", TokenClass::String));
+    assert!(has(&t, "Some", TokenClass::Constant));
+    assert!(has(&t, "1", TokenClass::Number));
+}
+
+#[test_log::test]
+fn highlight_python() {
+    let t = tokens(
+        "@cache\ndef f(x):\n    \"\"\"Doc.\"\"\"\n    return None  # Nothing.",
+        "python",
+    );
+    assert!(has(&t, "@cache", TokenClass::Function));
+    assert!(has(&t, "def", TokenClass::Keyword));
+    assert!(has(&t, "\"\"\"Doc.\"\"\"", TokenClass::String));
+    assert!(has(&t, "None", TokenClass::Constant));
+    assert!(has(&t, "# Nothing.", TokenClass::Comment));
+}
+
+#[test_log::test]
+fn highlight_shell() {
+    let t = tokens(
+        "if [ -n \"$HOME\" ]; then\n  echo ${USER} 'x' # Hi\nfi",
+        "bash",
+    );
+    assert!(has(&t, "if", TokenClass::Keyword));
+    assert!(has(&t, "\"$HOME\"", TokenClass::String));
+    assert!(has(&t, "${USER}", TokenClass::Variable));
+    assert!(has(&t, "# Hi", TokenClass::Comment));
+    assert!(has(&t, "fi", TokenClass::Keyword));
+}
+
+#[test_log::test]
+fn highlight_json() {
+    let t = tokens(r#"{"a": [1, true, null, "b"]}"#, "json");
+    assert!(has(&t, "\"a\"", TokenClass::Property));
+    assert!(has(&t, "\"b\"", TokenClass::String));
+    assert!(has(&t, "1", TokenClass::Number));
+    assert!(has(&t, "true", TokenClass::Constant));
+    assert!(has(&t, "null", TokenClass::Constant));
+}
+
+#[test_log::test]
+fn highlight_toml() {
+    let t = tokens(
+        "[package]\nname = \"durf\" # Name.\nedition.workspace = true\n",
+        "toml",
+    );
+    assert!(has(&t, "[package]", TokenClass::Type));
+    assert!(has(&t, "name", TokenClass::Property));
+    assert!(has(&t, "\"durf\"", TokenClass::String));
+    assert!(has(&t, "# Name.", TokenClass::Comment));
+    assert!(has(&t, "edition.workspace", TokenClass::Property));
+    assert!(has(&t, "true", TokenClass::Constant));
+}
+
+#[test_log::test]
+fn highlight_html() {
+    let t = tokens(
+        "<!doctype html>\n<!-- Hi -->\n<a href=\"/\" class=x>Home &amp; away</a>",
+        "html",
+    );
+    assert!(has(&t, "<!doctype html>", TokenClass::Keyword));
+    assert!(has(&t, "<!-- Hi -->", TokenClass::Comment));
+    assert!(has(&t, "a", TokenClass::Tag));
+    assert!(has(&t, "href", TokenClass::Attribute));
+    assert!(has(&t, "\"/\"", TokenClass::String));
+    assert!(has(&t, "x", TokenClass::String));
+    assert!(has(&t, "&amp;", TokenClass::Constant));
+}
+
+#[test_log::test]
+fn highlight_css() {
+    let t = tokens(
+        "@media print {\n  #header h1 { color: #fff; /* White. */ }\n}",
+        "css",
+    );
+    assert!(has(&t, "@media", TokenClass::Keyword));
+    assert!(has(&t, "color", TokenClass::Property));
+    assert!(has(&t, "#fff", TokenClass::Number));
+    assert!(has(&t, "/* White. */", TokenClass::Comment));
+
+    let t = tokens("#header h1 { display: block; }", "css");
+    assert!(has(&t, "#header", TokenClass::Tag));
+    assert!(has(&t, "h1", TokenClass::Tag));
+    assert!(has(&t, "display", TokenClass::Property));
+}
+
+#[test_log::test]
+fn highlight_javascript() {
+    let t = tokens(
+        "const x = `t${1}`; // C\nfunction f() { return new Map(); }",
+        "js",
+    );
+    assert!(has(&t, "const", TokenClass::Keyword));
+    assert!(has(&t, "`t${1}`", TokenClass::String));
+    assert!(has(&t, "// C", TokenClass::Comment));
+    assert!(has(&t, "f", TokenClass::Function));
+    assert!(has(&t, "Map", TokenClass::Function));
+}
+
+#[test_log::test]
+fn highlight_non_ascii() {
+    for language in ["python", "css", "js", "rust", "toml"] {
+        let source = "名前 = café → 1 @é ☃\n";
+        let mut code = Code::new(source, Some(language.into()));
+        assert!(code.highlight(), "{language}");
+        assert_eq!(code.text.collect(), source, "{language}");
+    }
+    let t = tokens("def 名前(é):\n    return é → 1", "python");
+    assert!(has(&t, "def", TokenClass::Keyword));
+    assert!(has(&t, "名前", TokenClass::Function));
+}
+
+#[test_log::test]
+fn highlight_unsupported() {
+    let mut code = Code::new("x", Some("cobol".into()));
+    assert!(!code.highlight());
+    assert!(!is_language_supported("cobol"));
+    assert!(is_language_supported("Rust"));
+}
+
+#[test_log::test]
+fn highlight_parsed_code() {
+    let page = r#"<pre><code class="language-json">{"a": 1}</code></pre>"#;
+    let ast = parse(page, ParseFlags::default());
+    let RawNode::Code(code) = &*ast.root.children()[0].children()[0] else {
+        panic!("Expected code in body");
+    };
+    assert!(
+        code.text
+            .fragments
+            .iter()
+            .any(|f| f.attributes.token.is_some())
+    );
+}
@@ -0,0 +1,93 @@
+//! Language and text direction tests.
+
+use super::*;
+
+#[test_log::test]
+fn capture_lang_and_dir() {
+    let page = r#"
+        <html lang="en"><body>
+            <p>Hello</p>
+            <div lang="ja">
+                <p>雪が<span lang="en">snow</span>降る</p>
+            </div>
+            <section dir="rtl" lang="ar">
+                <p>مرحبا</p>
+            </section>
+            <p>User <bdi>إيان</bdi> wrote <bdo dir="rtl">olleh</bdo></p>
+            <p dir="sideways">Invalid</p>
+        </body></html>
+        "#;
+    let mut ast = parse(page, ParseFlags::default());
+    assert_eq!(ast.lang.as_deref(), Some("en"));
+    assert_eq!(ast.dir, None);
+    assert!(ast.to_string().contains("Section [lang=ar] [dir=rtl]"));
+
+    ast.minimize();
+    let frags = fragments(&ast.root);
+    let frag = |text: &str| {
+        frags
+            .iter()
+            .find(|f| f.text.trim() == text)
+            .unwrap_or_else(|| panic!("missing {text}"))
+            .attributes
+            .clone()
+    };
+
+    assert_eq!(frag("Hello").lang.as_deref(), Some("en"));
+    assert_eq!(frag("雪が").lang.as_deref(), Some("ja"));
+    assert_eq!(frag("snow").lang.as_deref(), Some("en"));
+    assert_eq!(frag("降る").lang.as_deref(), Some("ja"));
+
+    let arabic = frag("مرحبا");
+    assert_eq!(arabic.lang.as_deref(), Some("ar"));
+    assert_eq!(arabic.dir, Some(Direction::Rtl));
+
+    let bdi = frag("إيان");
+    assert_eq!(bdi.dir, Some(Direction::Auto));
+    assert!(!bdi.bidi_override);
+    let bdo = frag("olleh");
+    assert_eq!(bdo.dir, Some(Direction::Rtl));
+    assert!(bdo.bidi_override);
+    assert_eq!(frag("User").dir, None);
+
+    assert_eq!(frag("Invalid").dir, None);
+    assert!(ast.report.fallbacks.is_empty(), "{}", ast.report);
+}
+
+#[test_log::test]
+fn text_direction() {
+    let ast = parse(
+        r#"<html dir="RTL"><body><p>שלום <b>עולם</b></p></body></html>"#,
+        ParseFlags::default(),
+    );
+    assert_eq!(ast.dir, Some(Direction::Rtl));
+    let mut directions = Vec::new();
+    let mut root = ast.root.clone();
+    root.for_each_text_mut(&mut |text| directions.push(text.direction()));
+    assert!(directions.contains(&Some(Direction::Rtl)));
+
+    let mut text = Text::from_fragment("mixed");
+    text.append(TextFragment::from("text"));
+    text.fragments[0].attributes.dir = Some(Direction::Rtl);
+    assert_eq!(text.direction(), None);
+}
+
+#[test_log::test]
+fn inherited_lang_is_shared() {
+    let ast = parse(
+        r#"<html lang="ja"><body><div><p>雪が<b>降る</b></p><p>寒い</p></div></body></html>"#,
+        ParseFlags::default(),
+    );
+    let mut langs = Vec::new();
+    let mut root = ast.root.clone();
+    root.for_each_text_mut(&mut |text| {
+        langs.extend(text.fragments.iter().map(|f| f.attributes.lang.clone()));
+    });
+    assert_eq!(langs.len(), 3);
+    let first = langs[0].clone().unwrap();
+    assert_eq!(&*first, "ja");
+    assert!(langs.iter().flatten().all(|lang| Arc::ptr_eq(lang, &first)));
+
+    // Only the section of the `html` element declares a language.
+    assert_eq!(ast.to_string().matches("[lang=ja]").count(), 1);
+}
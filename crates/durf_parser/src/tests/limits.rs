@@ -0,0 +1,213 @@
+//! Resource limit tests.
+
+use super::*;
+
+#[test_log::test]
+fn limit_nodes() {
+    let page = "<p>1</p>".repeat(100);
+    let mut ast = parse(
+        &page,
+        ParseFlags {
+            limits: ParseLimits {
+                max_nodes: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    let hit = ast.report.limit_hit(Limit::Nodes).unwrap();
+    assert_eq!(hit.path, "html > body");
+    assert!(hit.count > 80);
+    assert!(!ast.report.is_clean());
+    ast.minimize();
+    assert!(ast.root.node_count() <= 10);
+}
+
+#[test_log::test]
+fn limit_nodes_when_flattened() {
+    // Past the depth limit, the divs are flattened into blocks of text.
+    let page = format!(
+        "{}{}{}",
+        "<div>".repeat(12),
+        "<p>x</p>".repeat(300_000),
+        "</div>".repeat(12)
+    );
+    let mut ast = parse(
+        &page,
+        ParseFlags {
+            limits: ParseLimits {
+                max_nodes: 1000,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    assert!(ast.report.limit_hit(Limit::Nodes).is_some());
+    assert!(ast.report.nodes <= 1000);
+    ast.minimize();
+    assert!(ast.root.node_count() <= 1000);
+}
+
+#[test_log::test]
+fn limit_text_bytes() {
+    let page = "<p>0123456789</p>".repeat(10);
+    let ast = parse(
+        &page,
+        ParseFlags {
+            limits: ParseLimits {
+                max_text_bytes: 25,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    assert_eq!(ast.report.text_bytes, 25);
+    assert!(ast.report.limit_hit(Limit::TextBytes).is_some());
+    let export = ast.to_string();
+    assert_eq!(export.matches("0123456789").count(), 2);
+    assert!(export.contains("Text: 01234\n"));
+}
+
+#[test_log::test]
+fn limit_fragment_bytes() {
+    // Truncation keeps whole characters.
+    let page = "<p>日本語のテキスト</p><pre>fn main() {}</pre>";
+    let mut ast = parse(
+        page,
+        ParseFlags {
+            limits: ParseLimits {
+                max_fragment_bytes: 8,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    ast.minimize();
+    let hit = ast.report.limit_hit(Limit::FragmentBytes).unwrap();
+    assert_eq!(hit.path, "html > body > p");
+    assert_eq!(hit.count, 2);
+
+    let root = as_section(&ast.root);
+    let text = as_text(&root.nodes[0]);
+    assert_eq!(text.collect(), "日本");
+    let RawNode::Code(code) = &*root.nodes[1] else {
+        panic!("Expected code");
+    };
+    assert_eq!(code.text.collect(), "fn main(");
+}
+
+#[test_log::test]
+fn limit_attribute_bytes() {
+    let long = "x".repeat(100);
+    let page = format!(
+        r#"<p><a href="/{long}">Long</a> <a href="/short">Short</a></p>
+        <p style="color: red; /* {long} */">Styled</p>"#
+    );
+    let ast = parse(
+        &page,
+        ParseFlags {
+            limits: ParseLimits {
+                max_attribute_bytes: 50,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    let export = ast.to_string();
+    tracing::trace!("{export}");
+    assert!(export.contains("[Short](/short)"));
+    assert!(!export.contains(&long));
+    assert!(export.contains("Long"));
+    assert_eq!(
+        ast.report.limit_hit(Limit::AttributeBytes).unwrap().count,
+        2
+    );
+}
+
+#[test_log::test]
+fn limit_form_text() {
+    let long = "x".repeat(100);
+    let page = format!(
+        r#"<form>
+            <label for="q">{long}</label><input id="q" name="q">
+            <select name="s"><option>{long}</option></select>
+            <button>{long}</button>
+        </form>
+        <pre data-lang="{long}">code</pre>"#
+    );
+    let ast = parse(
+        &page,
+        ParseFlags {
+            limits: ParseLimits {
+                max_fragment_bytes: 10,
+                max_attribute_bytes: 50,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        ast.report.limit_hit(Limit::FragmentBytes).unwrap().count,
+        3,
+        "{ast}"
+    );
+    assert!(ast.report.limit_hit(Limit::AttributeBytes).is_some());
+    let form = find_form(&ast.root).unwrap();
+    let mut labels = Vec::new();
+    for node in &form.nodes {
+        if let RawNode::Field(field) = &**node {
+            labels.push(field.label.clone().unwrap_or_default());
+            if let FieldKind::Select(options) = &field.kind {
+                labels.extend(options.iter().map(|o| o.label.clone()));
+            }
+        }
+    }
+    assert_eq!(labels.len(), 4);
+    assert!(labels.iter().all(|label| label.len() <= 10), "{labels:?}");
+    assert_eq!(find_code(&ast.root).unwrap().language(), None);
+}
+
+#[test_log::test]
+fn limit_input_bytes() {
+    let page = "<p>Kept</p><p>Dropped</p>";
+    let ast = parse(
+        page,
+        ParseFlags {
+            limits: ParseLimits {
+                max_input_bytes: 12,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    assert_eq!(ast.report.input_bytes, page.len());
+    assert!(ast.report.limit_hit(Limit::InputBytes).is_some());
+    let export = ast.to_string();
+    assert!(export.contains("Kept"));
+    assert!(!export.contains("Dropped"));
+}
+
+#[test_log::test]
+fn limit_parse_time() {
+    let page = "<p>1</p>".repeat(100);
+    let ast = parse(
+        &page,
+        ParseFlags {
+            limits: ParseLimits {
+                max_parse_time: Some(std::time::Duration::ZERO),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    assert!(ast.report.limit_hit(Limit::ParseTime).is_some());
+    assert!(ast.report.nodes <= 1);
+    assert!(!ast.to_string().contains('1'));
+}
+
+#[test_log::test]
+fn default_limits_are_not_hit() {
+    let page = "<p>Text</p>".repeat(1000);
+    let ast = parse(&page, ParseFlags::default());
+    assert!(ast.report.limit_hits.is_empty());
+}
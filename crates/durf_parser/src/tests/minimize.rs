@@ -0,0 +1,92 @@
+//! Minimize tests.
+
+use super::*;
+
+const PAGE: &str = r#"
+    <html><body>
+        <div><div>
+            <p><span class="colorL">東北</span><span class="colorB">から</span><span class="colorB">まで</span><b>雪</b><ruby>日<rt>にち</rt></ruby><ruby>日<rt>ひ</rt></ruby></p>
+        </div>
+        <div lang="ja"><p>雪</p></div>
+        <ul><p>Only item</p></ul>
+        <br>
+        <p> <span> </span>Padded<span> </span></p>
+        </div>
+    </body></html>
+    "#;
+
+#[test_log::test]
+fn minimize_default() {
+    let mut ast = parse(PAGE, ParseFlags::default());
+    ast.minimize();
+    tracing::trace!("{ast}");
+
+    // Lists of one item are kept.
+    let root = as_section(&ast.root);
+    assert!(
+        root.nodes
+            .iter()
+            .any(|n| matches!(&**n, RawNode::Section(s) if s.ordering == SectionOrdering::List))
+    );
+
+    let texts = texts(&ast.root);
+    let collected: Vec<String> = texts.iter().map(|t| t.collect()).collect();
+    assert_eq!(
+        collected,
+        vec!["東北からまで雪日日", "雪", "Only item", "Padded"]
+    );
+
+    // Equal neighbours merge, while formatting and annotations are kept apart.
+    let frags: Vec<&str> = texts[0].fragments.iter().map(|f| f.text.as_str()).collect();
+    assert_eq!(frags, vec!["東北からまで", "雪", "日", "日"]);
+    let annotations: Vec<Option<&str>> = texts[0]
+        .fragments
+        .iter()
+        .map(|f| f.attributes.annotation.as_deref())
+        .collect();
+    assert_eq!(annotations, vec![None, None, Some("にち"), Some("ひ")]);
+
+    // Collapsed sections keep their language.
+    assert_eq!(texts[1].fragments[0].attributes.lang.as_deref(), Some("ja"));
+}
+
+#[test_log::test]
+fn minimize_keep_structure() {
+    let mut ast = parse(PAGE, ParseFlags::default());
+    ast.minimize_with(&MinimizeOptions::keep_structure());
+    tracing::trace!("{ast}");
+
+    let export = ast.to_string();
+    assert!(export.contains("Section\n"));
+    let texts = texts(&ast.root);
+    assert!(texts.iter().any(|t| t.collect() == "\n"));
+    assert!(texts.iter().any(|t| t.fragments.len() == 6));
+    assert!(texts.iter().any(|t| t.collect() == "  Padded "));
+}
+
+#[test_log::test]
+fn minimize_splice_and_hoist() {
+    let options = MinimizeOptions {
+        splice_sets: true,
+        hoist_text: true,
+        ..Default::default()
+    };
+    let mut ast = parse(PAGE, ParseFlags::default());
+    ast.minimize_with(&options);
+    tracing::trace!("{ast}");
+
+    // Nested sets are spliced into the root, and the list becomes its text.
+    let root = as_section(&ast.root);
+    assert!(root.nodes.iter().all(|n| matches!(&**n, RawNode::Text(_))));
+
+    let mut options = MinimizeOptions::keep_structure();
+    options.hoist_text = true;
+    let mut ast = parse("<ul><p>Only item</p></ul>", ParseFlags::default());
+    ast.minimize_with(&options);
+    let mut list = None;
+    ast.root
+        .clone()
+        .for_each_text_mut(&mut |t| list = Some(t.collect()));
+    assert_eq!(list.as_deref(), Some("Only item"));
+    assert!(!ast.to_string().contains("Section\n   Text"));
+}
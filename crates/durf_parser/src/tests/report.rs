@@ -0,0 +1,65 @@
+//! Parse report tests.
+
+use super::*;
+
+#[test_log::test]
+fn report_dropped_and_skipped() {
+    let page = r#"
+        <html><body>
+            <p>Text <sup>1</sup></p>
+            <video src="a.mp4"></video>
+            <video src="b.mp4"></video>
+            <canvas></canvas>
+            <div hidden><p>Hidden</p></div>
+            <details><p>No summary</p></details>
+        </body></html>
+        "#;
+    let ast = parse(page, ParseFlags::default());
+    let report = &ast.report;
+    tracing::trace!("{report}");
+
+    assert_eq!(report.input_bytes, page.len());
+    assert_eq!(report.nodes, ast.root.node_count());
+    assert!(report.elements >= 10);
+
+    assert_eq!(report.dropped.get("video"), Some(&2));
+    assert_eq!(report.dropped.get("canvas"), Some(&1));
+    assert_eq!(report.skipped.get("div"), Some(&1));
+    assert_eq!(report.not_rendered(), 3);
+    assert!(report.depth_hits.is_empty());
+
+    assert!(report.fallbacks.contains(&ParseFallback {
+        path: "html > body > p > sup".into(),
+        kind: FallbackKind::PlainText,
+    }));
+    assert!(report.fallbacks.contains(&ParseFallback {
+        path: "html > body > details".into(),
+        kind: FallbackKind::DefaultSummary,
+    }));
+    assert!(!report.is_clean());
+}
+
+#[test_log::test]
+fn report_depth_hits() {
+    let page = r#"<html><body><div><div><p>Deep <b>text</b></p></div></div></body></html>"#;
+    let flags = ParseFlags {
+        remaining_depth: 4,
+        depth_policy: DepthPolicy::Error,
+        ..Default::default()
+    };
+    let ast = parse(page, flags);
+    let report = &ast.report;
+    tracing::trace!("{report}");
+
+    assert_eq!(report.depth_hits, vec!["html > body > div > div > p"]);
+    assert_eq!(report.not_rendered(), 1);
+    assert!(!ast.to_string().contains("Deep"));
+}
+
+#[test_log::test]
+fn report_clean_document() {
+    let page = r#"<html><body><h1>Title</h1><p>Some <b>bold</b> text.</p></body></html>"#;
+    let ast = parse(page, ParseFlags::default());
+    assert!(ast.report.is_clean(), "{}", ast.report);
+    assert_eq!(ast.report.not_rendered(), 0);
+}
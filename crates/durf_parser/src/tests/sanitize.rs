@@ -0,0 +1,101 @@
+//! Control and bidi character sanitizing tests.
+
+use super::*;
+
+use std::borrow::Cow;
+
+fn assert_safe(text: &str) {
+    assert!(
+        !text
+            .chars()
+            .any(|c| is_unsafe_control(c) || is_bidi_control(c)),
+        "{text:?}"
+    );
+}
+
+#[test_log::test]
+fn sanitize_defaults() {
+    let page = "
+        <p>Title\u{1b}]0;pwned\u{7}</p>
+        <p>Clear\u{1b}[2J\u{9b}1;1H screen</p>
+        <p>evil\u{202e}txt.exe</p>
+        <p><a href=\"https://example.com/\u{1b}[5m\">Link\u{2066}</a></p>
+        <form action=\"/\u{1b}x\"><input name=\"q\" value=\"\u{1b}[31m\" placeholder=\"\u{202e}x\"></form>
+    ";
+    let ast = parse(page, ParseFlags::default());
+    let export = ast.to_string();
+    tracing::trace!("{export}");
+
+    assert_safe(&export);
+    assert_safe(&format!("{:?}", ast.root));
+    assert!(export.contains("Title␛]0;pwned␇"));
+    assert!(export.contains("Clear␛[2J<U+009B>1;1H screen"));
+    assert!(export.contains("eviltxt.exe"));
+}
+
+#[test_log::test]
+fn sanitize_attributes() {
+    let page = "<html lang=\"en\u{202e}\u{1b}[2J\"><body>
+        <div lang=\"ja\u{202e}\"><p>雪</p></div>
+        <p>Say <span lang=\"fr\u{200f}\">oui</span></p>
+        <form enctype=\"text/plain\u{1b}]0;x\u{7}\"><input name=\"q\"></form>
+        </body></html>";
+    let ast = parse(page, ParseFlags::default());
+    let export = ast.to_string();
+    assert_safe(&export);
+    assert_eq!(ast.lang.as_deref(), Some("en␛[2J"));
+    assert!(export.contains("[lang=ja]"));
+    assert!(
+        fragments(&ast.root)
+            .iter()
+            .any(|f| f.text == "oui" && f.attributes.lang.as_deref() == Some("fr"))
+    );
+    let form = find_form(&ast.root).unwrap();
+    assert_eq!(form.enctype, "text/plain␛]0;x␇");
+
+    // Nodes built in code are sanitized too.
+    let mut form = Form::new();
+    form.enctype = "\u{202e}text/plain".into();
+    let mut section = Section::new_set();
+    section.lang = Some("ar\u{2066}".into());
+    let mut text = Text::from_fragment("مرحبا");
+    text.fragments[0].attributes.lang = Some("ar\u{200e}".into());
+    section.nodes.push(Node::new(text.into()));
+    section.nodes.push(Node::new(form.into()));
+    let mut node = RawNode::from(section);
+    node.sanitize(&SanitizeFlags::default());
+    let node = Node::new(node);
+    let section = as_section(&node);
+    assert_eq!(section.lang.as_deref(), Some("ar"));
+    let text = as_text(&section.nodes[0]);
+    assert_eq!(text.fragments[0].attributes.lang.as_deref(), Some("ar"));
+    assert_eq!(find_form(&node).unwrap().enctype, "text/plain");
+}
+
+#[test_log::test]
+fn sanitize_modes() {
+    let mut flags = SanitizeFlags {
+        controls: SanitizeMode::Strip,
+        bidi: SanitizeMode::Escape,
+    };
+    assert_eq!(sanitize("a\u{1b}[1mb\u{85}", &flags), "a[1mb");
+    assert_eq!(sanitize("a\u{202e}b", &flags), "a<U+202E>b");
+    assert_eq!(sanitize("tab\tline\n", &flags), "tab\tline\n");
+    assert_eq!(
+        sanitize("\u{200E}a\u{200F}\u{061C}", &flags),
+        "<U+200E>a<U+200F><U+061C>"
+    );
+    assert!(matches!(sanitize("safe", &flags), Cow::Borrowed("safe")));
+
+    flags.controls = SanitizeMode::Keep;
+    flags.bidi = SanitizeMode::Keep;
+    assert_eq!(sanitize("a\u{1b}\u{202e}b", &flags), "a\u{1b}\u{202e}b");
+}
+
+#[test_log::test]
+fn sanitize_plain_text() {
+    let ast = Ast::from_text("one\u{1b}[2J\ntwo\u{2067}", ParseFlags::default()).unwrap();
+    let export = ast.to_string();
+    assert_safe(&export);
+    assert!(export.contains("one␛[2J"));
+}
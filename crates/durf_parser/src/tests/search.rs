@@ -0,0 +1,108 @@
+//! Full-text search tests.
+
+use super::*;
+
+fn page() -> Ast {
+    AstBuilder::new()
+        .heading(1, "Cats")
+        .para(|p| p.text("A cat can ").bold("concatenate").text(" Cat videos"))
+        .section("Cat facts", |s| s.text("Cats sleep 16 hours"))
+        .build()
+}
+
+fn spans(m: &SearchMatch) -> Vec<(usize, std::ops::Range<usize>)> {
+    m.spans
+        .iter()
+        .map(|s| (s.fragment, s.chars.clone()))
+        .collect()
+}
+
+#[test_log::test]
+fn search_case() {
+    let ast = page();
+    let matches = ast.search("cat", &SearchOptions::default()).unwrap();
+    assert_eq!(matches.len(), 6);
+    assert_eq!(matches[1].path, NodePath(vec![1]));
+    assert_eq!(spans(&matches[1]), [(0, 2..5)]);
+    assert_eq!(spans(&matches[2]), [(1, 3..6)]);
+
+    let options = SearchOptions {
+        case_sensitive: true,
+        ..Default::default()
+    };
+    let matches = ast.search("Cat", &options).unwrap();
+    assert_eq!(matches.len(), 4);
+    assert!(matches[2].in_title);
+    assert_eq!(matches[2].path, NodePath(vec![2]));
+    assert!(!matches[3].in_title);
+    assert_eq!(matches[3].path, NodePath(vec![2, 0]));
+}
+
+#[test_log::test]
+fn search_whole_word() {
+    let options = SearchOptions {
+        whole_word: true,
+        ..Default::default()
+    };
+    let matches = page().search("cat", &options).unwrap();
+    assert_eq!(matches.len(), 3);
+    assert_eq!(spans(&matches[1]), [(2, 1..4)]);
+}
+
+#[test_log::test]
+fn search_regex() {
+    let options = SearchOptions {
+        regex: true,
+        ..Default::default()
+    };
+    let matches = page().search(r"\d+ hours", &options).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(spans(&matches[0]), [(0, 11..19)]);
+
+    let err = page().search("(cat", &options).unwrap_err();
+    assert!(matches!(err, Error::InvalidQuery { .. }));
+    // Without regex, the query is literal.
+    assert!(
+        page()
+            .search("(cat", &SearchOptions::default())
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test_log::test]
+fn search_across_fragments() {
+    let ast = page();
+    let matches = ast.search("can conCAT", &SearchOptions::default()).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(spans(&matches[0]), [(0, 6..10), (1, 0..6)]);
+    assert!(
+        ast.search("", &SearchOptions::default())
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test_log::test]
+fn search_kana_and_width() {
+    let ast = AstBuilder::new()
+        .para(|p| p.text("カタカナと").bold("ＡＢＣ").text("とｶﾞｷﾞ"))
+        .build();
+    let options = SearchOptions {
+        ignore_kana_and_width: true,
+        ..Default::default()
+    };
+    assert!(
+        ast.search("かたかな", &SearchOptions::default())
+            .unwrap()
+            .is_empty()
+    );
+
+    let matches = ast.search("かたかな", &options).unwrap();
+    assert_eq!(spans(&matches[0]), [(0, 0..4)]);
+    let matches = ast.search("abc", &options).unwrap();
+    assert_eq!(spans(&matches[0]), [(1, 0..3)]);
+    // Halfwidth voiced marks are separate characters.
+    let matches = ast.search("がぎ", &options).unwrap();
+    assert_eq!(spans(&matches[0]), [(2, 1..5)]);
+}
@@ -0,0 +1,106 @@
+//! Section tests.
+
+use super::*;
+
+#[test_log::test]
+fn sectionize_headings() {
+    let page = r#"
+        <html><body>
+            <p>Intro</p>
+            <h1>One</h1>
+            <p>a</p>
+            <h2>One.One</h2>
+            <p>b</p>
+            <h2>One.Two</h2>
+            <p>c</p>
+            <h1>Two</h1>
+            <p>d</p>
+        </body></html>
+        "#;
+    let mut ast = parse(page, ParseFlags::default());
+    ast.minimize();
+    ast.sectionize();
+    tracing::trace!("{ast}");
+
+    let root = as_section(&ast.root);
+    assert_eq!(root.nodes.len(), 3);
+    assert!(matches!(&*root.nodes[0], RawNode::Text(t) if t.collect() == "Intro"));
+
+    let one = as_section(&root.nodes[1]);
+    assert_eq!(one.title.as_ref().unwrap().collect(), "One");
+    assert_eq!(one.nodes.len(), 3);
+    let one_one = as_section(&one.nodes[1]);
+    assert_eq!(one_one.title.as_ref().unwrap().collect(), "One.One");
+    assert_eq!(one_one.nodes.len(), 1);
+    let one_two = as_section(&one.nodes[2]);
+    assert_eq!(one_two.title.as_ref().unwrap().collect(), "One.Two");
+
+    let two = as_section(&root.nodes[2]);
+    assert_eq!(two.title.as_ref().unwrap().collect(), "Two");
+    assert_eq!(two.nodes.len(), 1);
+}
+
+#[test_log::test]
+fn sectionize_without_headings() {
+    let page = r#"<p>a</p><p>b</p>"#;
+    let mut ast = parse(page, ParseFlags::default());
+    ast.minimize();
+    ast.sectionize();
+
+    let root = as_section(&ast.root);
+    assert!(root.title.is_none());
+    assert_eq!(root.nodes.len(), 2);
+}
+
+#[test_log::test]
+fn titled_section_is_not_collapsed() {
+    let page = r#"<h2>Only</h2><p>a</p>"#;
+    let mut ast = parse(page, ParseFlags::default());
+    ast.minimize();
+    ast.sectionize();
+    ast.minimize();
+
+    let root = as_section(&ast.root);
+    assert_eq!(root.title.as_ref().unwrap().collect(), "Only");
+    assert_eq!(root.nodes.len(), 1);
+}
+
+#[test_log::test]
+fn section_kinds() {
+    let page = r#"
+        <html><body>
+            <nav><a href="/">Home</a></nav>
+            <div role="banner"><p>Site</p></div>
+            <main><p>Content</p></main>
+            <aside><p>Related</p></aside>
+            <footer><p>Copyright</p></footer>
+        </body></html>
+        "#;
+    let mut ast = parse(page, ParseFlags::default());
+    ast.minimize();
+    tracing::trace!("{ast}");
+
+    let root = as_section(&ast.root);
+    let kinds: Vec<SectionKind> = root.nodes.iter().map(|n| as_section(n).kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            SectionKind::Navigation,
+            SectionKind::Header,
+            SectionKind::Main,
+            SectionKind::Aside,
+            SectionKind::Footer,
+        ]
+    );
+
+    let main = ast.main_content().unwrap();
+    assert_eq!(main.kind, SectionKind::Main);
+}
+
+#[test_log::test]
+fn main_content_falls_back_to_article() {
+    let page = r#"<nav><a href="/">Home</a></nav><article><p>Story</p></article>"#;
+    let ast = parse(page, ParseFlags::default());
+    let main = ast.main_content().unwrap();
+    assert_eq!(main.kind, SectionKind::Article);
+}
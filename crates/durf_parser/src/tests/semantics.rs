@@ -0,0 +1,115 @@
+//! Semantic rule tests.
+
+use super::*;
+
+#[test_log::test]
+fn parse_semantic_rules() {
+    let page = r#"
+        <html><body>
+            <div class="article-title">Snow <span>expected</span></div>
+            <div class="ad">Buy now!</div>
+            <p>Run <span class="cmd">cargo test</span> or
+            <a class="btn" href="/go">Go</a>
+            <span class="pull-quote">Quoted</span>
+            <span class="gloss">note</span></p>
+            <div class="colorL"><p>Tokyo</p><p><span class="colorB">is</span></p></div>
+        </body></html>
+        "#;
+    let flags = ParseFlags::default()
+        .with_semantic(ParseRule::from_class("article-title"), Semantic::Heading(1))
+        .with_semantic(ParseRule::from_class("ad"), Semantic::Hidden)
+        .with_semantic(ParseRule::from_class("cmd"), Semantic::Code)
+        .with_semantic(ParseRule::from_class("pull-quote"), Semantic::Quote)
+        .with_semantic(ParseRule::from_class("gloss"), Semantic::Annotation)
+        .with_semantic(
+            ParseRule::from_class("btn"),
+            Semantic::Highlight("button".into()),
+        )
+        .with_semantic(
+            ParseRule::from_class("colorL"),
+            Semantic::Highlight("place".into()),
+        )
+        .with_semantic(
+            ParseRule::from_class("colorB"),
+            Semantic::Highlight("particle".into()),
+        );
+    let mut ast = parse(page, flags);
+    ast.minimize();
+    tracing::trace!("{ast}");
+
+    let export = ast.to_string();
+    assert!(!export.contains("Buy now"));
+
+    let root = as_section(&ast.root);
+    assert_eq!(root.nodes[0].heading_level(), Some(1));
+    let title = as_text(&root.nodes[0]);
+    assert_eq!(title.collect().trim(), "Snow expected");
+
+    let p = as_text(&root.nodes[1]);
+    let frag = |text: &str| {
+        p.fragments
+            .iter()
+            .find(|f| f.text == text)
+            .unwrap_or_else(|| panic!("Missing fragment {text}"))
+    };
+    assert!(frag("cargo test").attributes.preformatted);
+    assert!(frag("Quoted").attributes.preformatted);
+    assert_eq!(frag("Go").attributes.highlight.as_deref(), Some("button"));
+    assert_eq!(frag("Go").attributes.link.as_deref(), Some("/go"));
+    assert!(
+        p.fragments
+            .iter()
+            .any(|f| f.text.is_empty() && f.attributes.annotation.as_deref() == Some("note"))
+    );
+
+    // Highlights apply through sections, and inner highlights take priority.
+    let highlights: Vec<_> = fragments(&root.nodes[2])
+        .into_iter()
+        .map(|f| (f.text, f.attributes.highlight))
+        .collect();
+    assert_eq!(
+        highlights,
+        vec![
+            ("Tokyo".into(), Some("place".into())),
+            ("is".into(), Some("particle".into())),
+        ]
+    );
+}
+
+#[test_log::test]
+fn semantic_heading_levels_are_clamped() {
+    let page = r#"<div class="t">Title</div><div class="u">Sub</div>"#;
+    let flags = ParseFlags::default()
+        .with_semantic(ParseRule::from_class("t"), Semantic::Heading(0))
+        .with_semantic(ParseRule::from_element("div"), Semantic::Heading(9));
+    let mut ast = parse(page, flags);
+    ast.minimize();
+    let root = as_section(&ast.root);
+    assert_eq!(root.nodes[0].heading_level(), Some(1));
+    assert_eq!(root.nodes[1].heading_level(), Some(6));
+}
+
+#[test_log::test]
+fn semantic_rules_on_blocks_and_links() {
+    let page = r#"
+        <div><p>雪</p><div class="furigana">ゆき</div></div>
+        <a class="title" href="/snow">Snow</a>
+        "#;
+    let flags = ParseFlags::default()
+        .with_semantic(ParseRule::from_class("furigana"), Semantic::Annotation)
+        .with_semantic(ParseRule::from_class("title"), Semantic::Heading(2));
+    let ast = parse(page, flags);
+    assert!(ast.report.dropped.is_empty(), "{}", ast.report);
+
+    let texts = texts(&ast.root);
+    assert!(texts.iter().any(|t| {
+        t.fragments
+            .iter()
+            .any(|f| f.text.is_empty() && f.attributes.annotation.as_deref() == Some("ゆき"))
+    }));
+
+    // The heading keeps the link of the element it replaces.
+    let title = texts.iter().find(|t| t.collect() == "Snow").unwrap();
+    assert_eq!(title.heading_level(), Some(2));
+    assert_eq!(title.fragments[0].attributes.link.as_deref(), Some("/snow"));
+}
@@ -0,0 +1,74 @@
+//! Thread safety and structural sharing tests.
+
+use super::*;
+
+const PAGE: &str = r#"
+    <html><body>
+        <main><p>Shared <b>text</b></p></main>
+        <p>Other</p>
+    </body></html>
+    "#;
+
+#[test_log::test]
+fn clones_share_nodes() {
+    let ast = parse(PAGE, ParseFlags::default());
+    let mut copy = ast.clone();
+    assert!(copy.root.ptr_eq(&ast.root));
+
+    // Editing the copy only copies the path to the edit.
+    let RawNode::Section(root) = &mut *copy.root else {
+        panic!("Expected section root");
+    };
+    let RawNode::Section(body) = &mut *root.nodes[0] else {
+        panic!("Expected body");
+    };
+    let RawNode::Text(other) = &mut *body.nodes[1] else {
+        panic!("Expected text");
+    };
+    other.fragments[0].text = "Edited".into();
+
+    assert!(!copy.root.ptr_eq(&ast.root));
+    assert!(ast.to_string().contains("Other"));
+    assert!(!ast.to_string().contains("Edited"));
+    assert!(copy.to_string().contains("Edited"));
+
+    let (RawNode::Section(a), RawNode::Section(b)) = (&*ast.root, &*copy.root) else {
+        panic!("Expected section roots");
+    };
+    let (RawNode::Section(a), RawNode::Section(b)) = (&*a.nodes[0], &*b.nodes[0]) else {
+        panic!("Expected bodies");
+    };
+    assert!(a.nodes[0].ptr_eq(&b.nodes[0]));
+    assert!(!a.nodes[1].ptr_eq(&b.nodes[1]));
+}
+
+#[test_log::test]
+fn clones_share_language_and_report() {
+    let ast = parse(
+        r#"<html lang="en"><body><p>Text</p></body></html>"#,
+        ParseFlags::default(),
+    );
+    let copy = ast.clone();
+    assert!(Arc::ptr_eq(&copy.report, &ast.report));
+    assert!(Arc::ptr_eq(
+        copy.lang.as_ref().unwrap(),
+        ast.lang.as_ref().unwrap()
+    ));
+}
+
+#[test_log::test]
+fn parse_and_share_across_threads() {
+    let ast = std::thread::spawn(|| parse(PAGE, ParseFlags::default()))
+        .join()
+        .unwrap();
+    let ast = Arc::new(ast);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let ast = Arc::clone(&ast);
+            std::thread::spawn(move || ast.to_string())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), ast.to_string());
+    }
+}
@@ -0,0 +1,183 @@
+//! Incremental parsing tests.
+
+use super::*;
+
+const CHUNKS: [&str; 4] = [
+    "<html lang=\"en\"><body><h1>Title</h1><p>First",
+    " paragraph</p><p>Sec",
+    "ond</p><div><p>Nested</p>",
+    "</div></body></html>",
+];
+
+const UTF_8: Option<&str> = Some("text/html; charset=utf-8");
+
+#[test_log::test]
+fn stream_emits_finished_nodes() {
+    let mut parser = StreamParser::new(UTF_8, ParseFlags::default()).unwrap();
+    // The paragraph may still be open.
+    assert_eq!(parser.push(CHUNKS[0].as_bytes()).unwrap().len(), 1);
+    let nodes = parser.push(CHUNKS[1].as_bytes()).unwrap();
+    assert_eq!(nodes.len(), 1);
+    let text = as_text(&nodes[0]);
+    assert_eq!(text.collect(), "First paragraph");
+    assert_eq!(parser.push(CHUNKS[2].as_bytes()).unwrap().len(), 1);
+    assert!(parser.push(CHUNKS[3].as_bytes()).unwrap().is_empty());
+
+    let (remaining, ast) = parser.finish().unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(ast.lang.as_deref(), Some("en"));
+    let export = ast.to_string();
+    for text in ["Title", "First paragraph", "Second", "Nested"] {
+        assert!(export.contains(text), "Missing {text}");
+    }
+    assert_eq!(ast.report.input_bytes, CHUNKS.concat().len());
+}
+
+#[test_log::test]
+fn stream_matches_full_parse() {
+    let mut parser = StreamParser::new(UTF_8, ParseFlags::default()).unwrap();
+    let mut nodes = Vec::new();
+    for chunk in CHUNKS {
+        nodes.extend(parser.push(chunk.as_bytes()).unwrap());
+    }
+    let (remaining, mut ast) = parser.finish().unwrap();
+    nodes.extend(remaining);
+    assert_eq!(nodes.len(), 4);
+
+    let mut full = parse(&CHUNKS.concat(), ParseFlags::default());
+    ast.minimize();
+    full.minimize();
+    assert_eq!(ast.to_string(), full.to_string());
+}
+
+#[test_log::test]
+fn stream_inherits_language() {
+    let mut parser = StreamParser::new(UTF_8, ParseFlags::default()).unwrap();
+    parser
+        .push("<html><body lang=\"ja\"><p>雪</p><p>".as_bytes())
+        .unwrap();
+    let (_, ast) = parser.finish().unwrap();
+    let root = as_section(&ast.root);
+    let text = as_text(&root.nodes[0]);
+    assert_eq!(text.fragments[0].attributes.lang.as_deref(), Some("ja"));
+}
+
+#[test_log::test]
+fn stream_limits_input_bytes() {
+    let flags = ParseFlags {
+        limits: ParseLimits {
+            max_input_bytes: 12,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut parser = StreamParser::new(UTF_8, flags).unwrap();
+    parser.push("<p>Kept</p>".as_bytes()).unwrap();
+    parser.push("<p>Dropped</p>".as_bytes()).unwrap();
+    let (_, ast) = parser.finish().unwrap();
+    assert!(ast.report.limit_hit(Limit::InputBytes).is_some());
+    let export = ast.to_string();
+    assert!(export.contains("Kept"));
+    assert!(!export.contains("Dropped"));
+}
+
+#[test_log::test]
+fn stream_descends_into_lone_wrapper() {
+    let mut parser = StreamParser::new(UTF_8, ParseFlags::default()).unwrap();
+    let nodes = parser
+        .push(b"<body>\n<div id=\"app\"><p>First</p><p>Second</p><p>Th")
+        .unwrap();
+    assert_eq!(nodes.len(), 2);
+    let nodes = parser.push(b"ird</p></div><p>After</p>").unwrap();
+    assert_eq!(nodes.len(), 1);
+
+    let (remaining, ast) = parser.finish().unwrap();
+    assert_eq!(remaining.len(), 1);
+    let root = as_section(&ast.root);
+    // The wrapper keeps its children, followed by its sibling.
+    assert_eq!(root.nodes.len(), 2);
+    let wrapper = as_section(&root.nodes[0]);
+    assert_eq!(wrapper.nodes.len(), 3);
+}
+
+#[test_log::test]
+fn stream_emits_body_text() {
+    let mut parser = StreamParser::new(UTF_8, ParseFlags::default()).unwrap();
+    let nodes = parser.push(b"<body>Bare text<p>Para").unwrap();
+    assert_eq!(nodes.len(), 1);
+    let text = as_text(&nodes[0]);
+    assert_eq!(text.collect(), "Bare text");
+    let (_, ast) = parser.finish().unwrap();
+    assert!(ast.to_string().contains("Bare text"));
+}
+
+#[test_log::test]
+fn stream_decodes_split_characters() {
+    let html = "<html><head><meta charset=\"shift_jis\"></head><body><p>雪が降る</p></body></html>";
+    let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode(html);
+    let mut parser = StreamParser::new(None, ParseFlags::default()).unwrap();
+    let mut nodes = Vec::new();
+    for chunk in bytes.chunks(7) {
+        nodes.extend(parser.push(chunk).unwrap());
+    }
+    let (remaining, ast) = parser.finish().unwrap();
+    nodes.extend(remaining);
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(ast.report.encoding.as_ref().unwrap().name, "Shift_JIS");
+    assert!(ast.to_string().contains("雪が降る"));
+}
+
+#[test_log::test]
+fn stream_matches_full_parse_with_bare_text() {
+    let chunks = [
+        "<body>Bare <em>emphasised</em> text<div id=\"app\">Wrapped",
+        " text<p>Para</p>Trailing</div>After",
+        "</body>",
+    ];
+    let mut parser = StreamParser::new(UTF_8, ParseFlags::default()).unwrap();
+    for chunk in chunks {
+        parser.push(chunk.as_bytes()).unwrap();
+    }
+    let (_, mut ast) = parser.finish().unwrap();
+
+    let mut full = parse(&chunks.concat(), ParseFlags::default());
+    ast.minimize();
+    full.minimize();
+    assert_eq!(ast.to_string(), full.to_string());
+    for text in ["Bare", "emphasised", "Wrapped text", "Trailing", "After"] {
+        assert!(full.to_string().contains(text), "Missing {text}");
+    }
+}
+
+#[test_log::test]
+fn stream_labels_fields_of_later_chunks() {
+    let mut parser = StreamParser::new(UTF_8, ParseFlags::default()).unwrap();
+    parser
+        .push(b"<body><form><p><label for=\"q\">Query</label></p><p>")
+        .unwrap();
+    parser
+        .push(b"<input id=\"q\" name=\"q\"></p></form>")
+        .unwrap();
+    let (_, ast) = parser.finish().unwrap();
+    let form = find_form(&ast.root).unwrap();
+    assert_eq!(form.fields()[0].label.as_deref(), Some("Query"));
+}
+
+#[test_log::test]
+fn stream_limits_parse_time() {
+    let flags = ParseFlags {
+        limits: ParseLimits {
+            max_parse_time: Some(std::time::Duration::ZERO),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut parser = StreamParser::new(UTF_8, flags).unwrap();
+    for _ in 0..10 {
+        assert!(parser.push(b"<p>1</p>").unwrap().is_empty());
+    }
+    let (remaining, ast) = parser.finish().unwrap();
+    assert!(remaining.is_empty());
+    assert!(ast.report.limit_hit(Limit::ParseTime).is_some());
+    assert!(!ast.to_string().contains('1'));
+}
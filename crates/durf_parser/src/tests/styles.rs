@@ -0,0 +1,127 @@
+//! Inline style and class style tests.
+
+use super::*;
+
+#[test_log::test]
+fn parse_colors() {
+    assert_eq!(Color::parse("#f80"), Some(Color::new(255, 136, 0)));
+    assert_eq!(Color::parse("#FF8800cc"), Some(Color::new(255, 136, 0)));
+    assert_eq!(
+        Color::parse("rgb(255, 136, 0)"),
+        Some(Color::new(255, 136, 0))
+    );
+    assert_eq!(
+        Color::parse("rgba(100% 0% 0% / 50%)"),
+        Some(Color::new(255, 0, 0))
+    );
+    assert_eq!(Color::parse(" Navy "), Some(Color::new(0, 0, 128)));
+    assert_eq!(Color::parse("transparent"), None);
+    assert_eq!(Color::parse("#12"), None);
+    assert_eq!(Color::new(255, 136, 0).to_string(), "#ff8800");
+}
+
+#[test_log::test]
+fn parse_css_declarations() {
+    let style = TextStyle::from_css(
+        "color: red !important; background: url(x.png) #000 no-repeat; font-weight: 300; font-style: italic; margin: 0",
+    );
+    assert_eq!(style.fg, Some(Color::new(255, 0, 0)));
+    assert_eq!(style.bg, Some(Color::new(0, 0, 0)));
+    assert_eq!(style.weight, Some(300));
+    assert_eq!(style.italic, Some(true));
+
+    let style = TextStyle::from_css("color: inherit; font-weight: bold");
+    assert_eq!(style.fg, None);
+    assert_eq!(style.weight, Some(700));
+    assert!(TextStyle::from_css("display: none").is_empty());
+}
+
+#[test_log::test]
+fn parse_inline_styles() {
+    let page = r#"
+        <p style="color: #00f">Blue <span style="color: red; font-weight: bold">red</span></p>
+        <p><span style="font-weight: normal">Plain</span></p>
+        "#;
+    let frags = fragments(&parse(page, ParseFlags::default()).root);
+    assert_eq!(frags.len(), 3);
+
+    assert_eq!(frags[0].text, "Blue ");
+    assert_eq!(frags[0].attributes.fg, Some(Color::new(0, 0, 255)));
+    assert!(!frags[0].attributes.bold);
+
+    // The inner style takes priority.
+    assert_eq!(frags[1].text, "red");
+    assert_eq!(frags[1].attributes.fg, Some(Color::new(255, 0, 0)));
+    assert_eq!(frags[1].attributes.weight, Some(700));
+    assert!(frags[1].attributes.bold);
+
+    assert_eq!(frags[2].attributes.weight, Some(400));
+    assert!(!frags[2].attributes.bold);
+    assert!(!frags[2].attributes.is_plain());
+}
+
+#[test_log::test]
+fn normal_styles_override_inherited() {
+    let page = r#"
+        <p><i>Slanted <span style="font-style: normal">upright</span></i></p>
+        <p style="font-weight: normal; font-style: normal">Plain <b>bold</b> <em>italic</em></p>
+        <p><b style="font-weight: normal">Unbold</b></p>
+        "#;
+    let frags = fragments(&parse(page, ParseFlags::default()).root);
+    let frag = |text: &str| {
+        frags
+            .iter()
+            .find(|frag| frag.text.trim() == text)
+            .unwrap_or_else(|| panic!("missing {text}"))
+    };
+
+    assert!(frag("Slanted").attributes.italic);
+    assert!(!frag("upright").attributes.italic);
+    assert_eq!(frag("upright").attributes.italic_style, Some(false));
+
+    // The element's own bold and italic win over ancestor styles.
+    assert_eq!(frag("Plain").attributes.weight, Some(400));
+    assert!(frag("bold").attributes.bold);
+    assert_eq!(frag("bold").attributes.weight, None);
+    assert!(frag("italic").attributes.italic);
+    assert_eq!(frag("italic").attributes.italic_style, None);
+
+    // But its own style wins over the tag.
+    assert!(!frag("Unbold").attributes.bold);
+    assert_eq!(frag("Unbold").attributes.weight, Some(400));
+}
+
+#[test_log::test]
+fn parse_class_styles() {
+    let page = r#"
+        <p><span class="colorL"><ruby>日本海<rt>にほんかい</rt></ruby></span><span class="colorB">で</span><span class="colorL" style="color: blue">雪</span></p>
+        "#;
+    let flags = ParseFlags::default()
+        .with_class_style("colorL", TextStyle::new().with_fg(Color::new(0, 128, 0)))
+        .with_class_style("colorB", TextStyle::new().with_weight(700));
+    let frags = fragments(&parse(page, flags).root);
+    assert_eq!(frags.len(), 3);
+
+    assert_eq!(frags[0].text, "日本海");
+    assert_eq!(
+        frags[0].attributes.annotation.as_deref(),
+        Some("にほんかい")
+    );
+    assert_eq!(frags[0].attributes.fg, Some(Color::new(0, 128, 0)));
+
+    assert_eq!(frags[1].attributes.fg, None);
+    assert!(frags[1].attributes.bold);
+
+    // Inline styles take priority over class styles.
+    assert_eq!(frags[2].attributes.fg, Some(Color::new(0, 0, 255)));
+}
+
+#[test_log::test]
+fn colors_deserialize_from_css() {
+    use serde::de::{IntoDeserializer, value::Error};
+
+    let color = Color::deserialize(IntoDeserializer::<Error>::into_deserializer("#c00"));
+    assert_eq!(color.unwrap(), Color::new(204, 0, 0));
+    let color = Color::deserialize(IntoDeserializer::<Error>::into_deserializer("nope"));
+    assert!(color.is_err());
+}
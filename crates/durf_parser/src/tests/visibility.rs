@@ -0,0 +1,74 @@
+//! Hidden and non-rendered content tests.
+
+use super::*;
+
+const PAGE: &str = r#"
+    <html><head><style>p { color: red; }</style></head><body>
+        <p>Shown <span hidden>hidden</span><script>var x = 1;</script><style>.a {}</style>text</p>
+        <div hidden><p>Hidden attribute</p></div>
+        <div style="margin: 0; DISPLAY: None !important"><p>Display none</p></div>
+        <p style="visibility: hidden">Visibility hidden</p>
+        <p aria-hidden="true">Aria hidden</p>
+        <p aria-hidden="false">Aria shown</p>
+        <template><p>Template</p></template>
+        <noscript><p>Enable <b>JavaScript</b></p></noscript>
+        <p>Inline <noscript>fallback</noscript></p>
+    </body></html>
+    "#;
+
+#[test_log::test]
+fn skip_hidden_content() {
+    let ast = parse(PAGE, ParseFlags::default());
+    let export = ast.to_string();
+    tracing::trace!("{export}");
+
+    assert!(export.contains("Shown text"));
+    assert!(export.contains("Aria shown"));
+    for hidden in [
+        "var x",
+        ".a {}",
+        "color: red",
+        "Hidden attribute",
+        "Display none",
+        "Visibility hidden",
+        "Aria hidden",
+        "Template",
+    ] {
+        assert!(!export.contains(hidden), "Found `{hidden}`");
+    }
+    assert!(!export.contains("Shown hidden"));
+
+    // Noscript content is parsed as markup.
+    assert!(export.contains("Enable **JavaScript**"));
+    assert!(!export.contains("<p>"));
+    assert!(export.contains("Inline fallback"));
+}
+
+#[test_log::test]
+fn show_hidden_content_with_flags() {
+    let flags = ParseFlags {
+        skip_hidden: false,
+        skip_aria_hidden: false,
+        noscript: false,
+        ..Default::default()
+    };
+    let ast = parse(PAGE, flags);
+    let export = ast.to_string();
+    tracing::trace!("{export}");
+
+    assert!(export.contains("Shown hidden"));
+    assert!(export.contains("Hidden attribute"));
+    assert!(export.contains("Display none"));
+    assert!(export.contains("Visibility hidden"));
+    assert!(export.contains("Aria hidden"));
+    assert!(!export.contains("JavaScript"));
+    assert!(!export.contains("fallback"));
+    assert!(!export.contains("var x"));
+
+    let flags = ParseFlags {
+        skip_scripts: false,
+        ..Default::default()
+    };
+    let export = parse(PAGE, flags).to_string();
+    assert!(export.contains("var x"));
+}
@@ -6,7 +6,7 @@ use ratatui::{DefaultTerminal, widgets::Widget};
 
 use durf_ratatui::prelude::*;
 
-const BODY: &'static str = "
+const BODY: &str = "
     <html>
         <body>
             <h1>Hello</h1>
@@ -18,6 +18,10 @@ const BODY: &'static str = "
                     <p><em>Emphasis</em> mine.</p>
                     <p>Honestly, I may need to do something cool <a href=\"https://hachha.dev\">here</a>, but I'm not sure what <code>I should do.</code></p>
                 </div>
+                <details>
+                    <summary>Click to expand</summary>
+                    <p>Hidden until <b>toggled</b>.</p>
+                </details>
                 <div>
                     <h1>HTML Ipsum Presents</h1>
 
@@ -39,7 +43,7 @@ const BODY: &'static str = "
     				   <li>Aliquam tincidunt mauris eu risus.</li>
     				</ul>
 
-    				<pre><code>
+    				<pre><code class=\"language-css\">
     				#header h1 a {
     				  display: block;
     				  width: 300px;
@@ -126,15 +130,13 @@ fn run(mut terminal: DefaultTerminal) -> Result<()> {
                     event::MouseEventKind::ScrollDown => {
                         state.scroll(4);
                     }
-                    event::MouseEventKind::Down(button) => {
-                        if button.is_left() {
-                            let mut widget = DurfWidget {
-                                ast: &ast,
-                                state: &mut state,
-                                style: &mut style,
-                            };
-                            widget.handle_click(Position::new(mouse.column, mouse.row));
-                        }
+                    event::MouseEventKind::Down(button) if button.is_left() => {
+                        let mut widget = DurfWidget {
+                            ast: &ast,
+                            state: &mut state,
+                            style: &mut style,
+                        };
+                        widget.handle_click(Position::new(mouse.column, mouse.row));
                     }
                     _ => {}
                 },
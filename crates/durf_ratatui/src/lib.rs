@@ -9,3 +9,6 @@ mod widget;
 
 pub(crate) use prelude::internal::*;
 use prelude::*;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+use super::*;
+
+/// Spaces rendered for each tab in code.
+const TAB_WIDTH: usize = 4;
+
+impl WidgetSize for durf_parser::Code {
+    fn height(
+        &self,
+        area: ratatui::prelude::Rect,
+        style: &DurfWidgetStyle,
+        _ctx: &mut SizeContext,
+    ) -> usize {
+        if area.width == 0 {
+            return 0;
+        }
+
+        code_lines(self, style)
+            .iter()
+            .map(|line| line.width().div_ceil(area.width as usize).max(1))
+            .sum()
+    }
+}
+
+/// Render a code block line by line.
+pub(crate) fn render_code(
+    code: &durf_parser::Code,
+    area: Rect,
+    buf: &mut Buffer,
+    style: &DurfWidgetStyle,
+    ctx: &mut DurfNodeWidgetContext,
+) {
+    let text = ratatui::text::Text::from(code_lines(code, style));
+    let p = Paragraph::new(text).wrap(Wrap { trim: false });
+    p.render(area, buf);
+    ctx.index += 1;
+}
+
+/// Split code fragments into lines of spans, colored by token.
+fn code_lines(
+    code: &durf_parser::Code,
+    style: &DurfWidgetStyle,
+) -> Vec<ratatui::text::Line<'static>> {
+    let mut lines = vec![ratatui::text::Line::default()];
+    for frag in &code.text.fragments {
+        for (i, part) in frag.text.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(ratatui::text::Line::default());
+            }
+            if part.is_empty() {
+                continue;
+            }
+            let mut span = ratatui::text::Span::raw(part.replace('\t', &" ".repeat(TAB_WIDTH)));
+            if let Some(token) = frag.attributes.token {
+                span = span.fg(style.general.code.token_color(token));
+            }
+            if frag.attributes.bold {
+                span = span.bold();
+            }
+            if frag.attributes.italic {
+                span = span.italic();
+            }
+            if let Some(line) = lines.last_mut() {
+                line.push_span(span);
+            }
+        }
+    }
+
+    // A trailing line break does not start a new line.
+    if lines.len() > 1 && lines.last().is_some_and(|l| l.spans.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+/// Width of the indent for open disclosure content.
+const DETAILS_INDENT: u16 = 2;
+
+impl WidgetSize for durf_parser::Details {
+    fn height(
+        &self,
+        area: ratatui::prelude::Rect,
+        style: &DurfWidgetStyle,
+        ctx: &mut SizeContext,
+    ) -> usize {
+        let ordinal = ctx.disclosure;
+        ctx.disclosure += 1;
+        let open = ctx.state.is_disclosure_open(ordinal, self.open);
+
+        let mut total = disclosure_summary(self, open).height(area, style, ctx);
+        if !open {
+            ctx.disclosure += count_disclosures(&self.nodes);
+            return total;
+        }
+
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: area.width.saturating_sub(DETAILS_INDENT),
+            height: 0,
+        };
+        for node in &self.nodes {
+            total += node.height(area, style, ctx);
+        }
+        total
+    }
+}
+
+/// Render a disclosure, with its content if open.
+pub(crate) fn render_details(
+    details: &durf_parser::Details,
+    area: Rect,
+    buf: &mut Buffer,
+    state: &mut DurfWidgetState,
+    style: &DurfWidgetStyle,
+    ctx: &mut DurfNodeWidgetContext,
+) {
+    let ordinal = ctx.disclosure;
+    ctx.disclosure += 1;
+    let open = state.is_disclosure_open(ordinal, details.open);
+
+    // Render summary, which toggles the disclosure.
+    let summary = disclosure_summary(details, open);
+    let summary_height = summary.height(area, style, &mut SizeContext::new(state, 0)) as u16;
+    let mut focus = FocusableNode::new(ctx.index);
+    focus.disclosure = Some(ordinal);
+    focus
+        .rect
+        .push(Rect::new(area.x, area.y, area.width, summary_height.max(1)));
+    state.focusable.push(focus);
+    render_text(&summary, area, buf, state, style, ctx);
+
+    // Render content.
+    if open {
+        let inner = Block::new()
+            .padding(Padding {
+                left: DETAILS_INDENT,
+                right: 0,
+                top: 0,
+                bottom: 0,
+            })
+            .inner(Rect {
+                y: area.y.saturating_add(summary_height),
+                height: area.height.saturating_sub(summary_height),
+                ..area
+            });
+        render_nodes(&details.nodes, inner, buf, state, style, ctx);
+    } else {
+        ctx.disclosure += count_disclosures(&details.nodes);
+    }
+
+    ctx.index += 1;
+}
+
+/// Summary text with an open/closed marker.
+fn disclosure_summary(details: &durf_parser::Details, open: bool) -> durf_parser::Text {
+    let mut summary = details.summary.clone();
+    let marker = match open {
+        true => "▼ ",
+        false => "▶ ",
+    };
+    summary
+        .fragments
+        .insert(0, durf_parser::TextFragment::from(marker));
+    summary
+}
+
+/// Count disclosures within nodes, recursively.
+pub(crate) fn count_disclosures(nodes: &[durf_parser::Node]) -> usize {
+    nodes
+        .iter()
+        .map(|node| match &**node {
+            durf_parser::RawNode::Details(d) => 1 + count_disclosures(&d.nodes),
+            other => count_disclosures(other.children()),
+        })
+        .sum()
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+impl WidgetSize for durf_parser::Form {
+    fn height(
+        &self,
+        area: ratatui::prelude::Rect,
+        style: &DurfWidgetStyle,
+        ctx: &mut SizeContext,
+    ) -> usize {
+        self.nodes
+            .iter()
+            .map(|node| node.height(area, style, ctx))
+            .sum()
+    }
+}
+
+impl WidgetSize for durf_parser::Field {
+    fn height(
+        &self,
+        area: ratatui::prelude::Rect,
+        style: &DurfWidgetStyle,
+        ctx: &mut SizeContext,
+    ) -> usize {
+        field_text(self).height(area, style, ctx)
+    }
+}
+
+/// Render a field as text.
+pub(crate) fn render_field(
+    field: &durf_parser::Field,
+    area: Rect,
+    buf: &mut Buffer,
+    state: &mut DurfWidgetState,
+    style: &DurfWidgetStyle,
+    ctx: &mut DurfNodeWidgetContext,
+) {
+    render_text(&field_text(field), area, buf, state, style, ctx);
+}
+
+/// Text representation of a field.
+fn field_text(field: &durf_parser::Field) -> durf_parser::Text {
+    use durf_parser::{FieldKind, Text, TextAttributes, TextFragment};
+
+    let mut text = Text::new();
+    let label = field.label.as_deref().unwrap_or_default();
+    let boxed = |value: &str| {
+        let mut attributes = TextAttributes::new();
+        attributes.preformatted = true;
+        TextFragment::new(format!("[{value}]"), Some(attributes))
+    };
+    let value = match field.value.is_empty() {
+        true => field.placeholder.as_deref().unwrap_or_default(),
+        false => field.value.as_str(),
+    };
+
+    match &field.kind {
+        FieldKind::Hidden => {}
+        FieldKind::Text | FieldKind::Search | FieldKind::TextArea => {
+            if !label.is_empty() {
+                text.append(TextFragment::new(format!("{label} "), None));
+            }
+            text.append(boxed(value));
+        }
+        FieldKind::Password => {
+            if !label.is_empty() {
+                text.append(TextFragment::new(format!("{label} "), None));
+            }
+            text.append(boxed(&"*".repeat(field.value.chars().count())));
+        }
+        FieldKind::Checkbox | FieldKind::Radio => {
+            let marker = match (&field.kind, field.checked) {
+                (FieldKind::Checkbox, true) => "[x] ",
+                (FieldKind::Checkbox, false) => "[ ] ",
+                (_, true) => "(*) ",
+                (_, false) => "( ) ",
+            };
+            text.append(TextFragment::from(marker));
+            text.append(TextFragment::from(label));
+        }
+        FieldKind::Submit | FieldKind::Button => {
+            let label = match (label.is_empty(), field.value.is_empty()) {
+                (false, _) => label,
+                (true, false) => field.value.as_str(),
+                (true, true) => "Submit",
+            };
+            text.append(boxed(&format!(" {label} ")));
+        }
+        FieldKind::Select(options) => {
+            if !label.is_empty() {
+                text.append(TextFragment::new(format!("{label} "), None));
+            }
+            let selected: Vec<&str> = options
+                .iter()
+                .filter(|o| o.selected)
+                .map(|o| o.label.as_str())
+                .collect();
+            text.append(boxed(&format!("{} ▾", selected.join(", "))));
+        }
+    }
+
+    text
+}
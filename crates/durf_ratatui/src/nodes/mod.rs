@@ -1,8 +1,17 @@
 use super::*;
 
+mod code;
+mod details;
+mod form;
 mod section;
 mod text;
 
+#[allow(unused)]
+pub(crate) use code::*;
+#[allow(unused)]
+pub(crate) use details::*;
+#[allow(unused)]
+pub(crate) use form::*;
 #[allow(unused)]
 pub(crate) use section::*;
 #[allow(unused)]
@@ -33,35 +42,20 @@ impl<'a> Widget for DurfNodeWidget<'a> {
                 self.ctx.index += 1;
             }
             durf_parser::RawNode::Text(text) => {
-                let mut total_text_len = 0usize;
-                let line = ratatui::text::Line::from_iter(text.fragments.iter().map(|f| {
-                    let mut span = frag_to_span(f);
-                    if let Some(index) = self.state.focused_element {
-                        if index == self.ctx.index {
-                            span = span.bg(Color::Black).fg(Color::White);
-                        }
-                    }
-                    if let Some(_) = &f.attributes.link {
-                        span = span.underlined();
-                        let mut focus = FocusableNode::new(self.ctx.index);
-                        {
-                            // Add rects for focus.
-                            // TODO: Calculate more intelligently than starting line
-                            // number.
-                            let line: u16 = (total_text_len as u16).div_euclid(area.width) + area.y;
-                            focus.rect.push(Rect::new(area.x, line, area.width, 1));
-                        }
-                        self.state.focusable.push(focus);
-                    }
-                    self.ctx.index += 1;
-                    total_text_len += span.content.len();
-                    span
-                }));
-                let text = ratatui::text::Text::from(vec![line]);
-                let p = Paragraph::new(text).wrap(Wrap { trim: false });
-                p.render(area, buf);
+                render_text(text, area, buf, self.state, self.style, self.ctx);
             }
             durf_parser::RawNode::Section(section) => {
+                let chrome = match section.kind.is_chrome() {
+                    true => &self.style.general.section.chrome,
+                    false => &ChromeStyle::Show,
+                };
+                if let ChromeStyle::Hide = chrome {
+                    self.ctx.disclosure += count_disclosures(&section.nodes);
+                    self.ctx.index += 1;
+                    return;
+                }
+                let first_disclosure = self.ctx.disclosure;
+
                 // Render outline.
                 let block = match self.style.general.section.border {
                     BorderStyle::None => Block::new(),
@@ -84,41 +78,131 @@ impl<'a> Widget for DurfNodeWidget<'a> {
                 let mut inner_block = block.inner(area);
                 block.render(area, buf);
 
+                // Render title.
+                if let Some(title) = &section.title {
+                    render_text(title, inner_block, buf, self.state, self.style, self.ctx);
+                    let title_height = title.height(
+                        inner_block,
+                        self.style,
+                        &mut SizeContext::new(self.state, 0),
+                    );
+                    inner_block.height = inner_block.height.saturating_sub(title_height as u16);
+                    inner_block.y = inner_block.y.saturating_add(title_height as u16);
+                }
+
                 // Render inner area.
-                for inner_node in &section.nodes {
-                    self.ctx.offset += 1;
-                    let node_widget = DurfNodeWidget {
-                        node: inner_node,
-                        state: self.state,
-                        style: self.style,
-                        ctx: self.ctx,
-                    };
-                    node_widget.render(inner_block, buf);
-                    let max_rendered_height = inner_node.height(inner_block, self.style);
-                    inner_block.height = inner_block
-                        .height
-                        .saturating_sub(max_rendered_height as u16);
-                    inner_block.y = inner_block.y.saturating_add(max_rendered_height as u16);
+                match section.ordering {
+                    durf_parser::SectionOrdering::Definition => render_definitions(
+                        section,
+                        inner_block,
+                        buf,
+                        self.state,
+                        self.style,
+                        self.ctx,
+                    ),
+                    _ => render_nodes(
+                        &section.nodes,
+                        inner_block,
+                        buf,
+                        self.state,
+                        self.style,
+                        self.ctx,
+                    ),
+                }
+
+                if let ChromeStyle::Dim = chrome {
+                    let mut dim_area = area.intersection(buf.area);
+                    let mut size_ctx = SizeContext::new(self.state, first_disclosure);
+                    let height = section.height(area, self.style, &mut size_ctx);
+                    dim_area.height = dim_area.height.min(height as u16);
+                    buf.set_style(dim_area, ratatui::style::Modifier::DIM);
                 }
 
                 self.ctx.index += 1;
             }
+            durf_parser::RawNode::Details(details) => {
+                render_details(details, area, buf, self.state, self.style, self.ctx);
+            }
+            durf_parser::RawNode::Form(form) => {
+                render_nodes(&form.nodes, area, buf, self.state, self.style, self.ctx);
+                self.ctx.index += 1;
+            }
+            durf_parser::RawNode::Field(field) => {
+                render_field(field, area, buf, self.state, self.style, self.ctx);
+            }
+            durf_parser::RawNode::Code(code) => {
+                render_code(code, area, buf, self.style, self.ctx);
+            }
         }
     }
 }
 
+/// Render nodes top to bottom in the area.
+pub(crate) fn render_nodes(
+    nodes: &[durf_parser::Node],
+    mut area: Rect,
+    buf: &mut Buffer,
+    state: &mut DurfWidgetState,
+    style: &DurfWidgetStyle,
+    ctx: &mut DurfNodeWidgetContext,
+) {
+    for node in nodes {
+        ctx.offset += 1;
+        let max_rendered_height =
+            node.height(area, style, &mut SizeContext::new(state, ctx.disclosure));
+        let node_widget = DurfNodeWidget {
+            node,
+            state,
+            style,
+            ctx,
+        };
+        node_widget.render(area, buf);
+        area.height = area.height.saturating_sub(max_rendered_height as u16);
+        area.y = area.y.saturating_add(max_rendered_height as u16);
+    }
+}
+
 /// Internal trait for determining the size of widgets.
 pub(crate) trait WidgetSize {
     /// Get the height of a widget.
-    fn height(&self, area: ratatui::prelude::Rect, style: &DurfWidgetStyle) -> usize;
+    fn height(
+        &self,
+        area: ratatui::prelude::Rect,
+        style: &DurfWidgetStyle,
+        ctx: &mut SizeContext,
+    ) -> usize;
+}
+
+/// Context for sizing widgets.
+/// Disclosures are counted in document order, matching the render context.
+pub(crate) struct SizeContext<'a> {
+    /// Widget state.
+    pub(crate) state: &'a DurfWidgetState,
+    /// Ordinal of the next disclosure.
+    pub(crate) disclosure: usize,
+}
+
+impl<'a> SizeContext<'a> {
+    pub(crate) fn new(state: &'a DurfWidgetState, disclosure: usize) -> Self {
+        Self { state, disclosure }
+    }
 }
 
 impl WidgetSize for durf_parser::Node {
-    fn height(&self, area: ratatui::prelude::Rect, style: &DurfWidgetStyle) -> usize {
+    fn height(
+        &self,
+        area: ratatui::prelude::Rect,
+        style: &DurfWidgetStyle,
+        ctx: &mut SizeContext,
+    ) -> usize {
         match &**self {
             durf_parser::RawNode::Empty => 0,
-            durf_parser::RawNode::Section(s) => s.height(area, style),
-            durf_parser::RawNode::Text(t) => t.height(area, style),
+            durf_parser::RawNode::Section(s) => s.height(area, style, ctx),
+            durf_parser::RawNode::Text(t) => t.height(area, style, ctx),
+            durf_parser::RawNode::Details(d) => d.height(area, style, ctx),
+            durf_parser::RawNode::Form(f) => f.height(area, style, ctx),
+            durf_parser::RawNode::Field(f) => f.height(area, style, ctx),
+            durf_parser::RawNode::Code(c) => c.height(area, style, ctx),
         }
     }
 }
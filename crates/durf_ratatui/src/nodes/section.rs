@@ -1,7 +1,19 @@
 use super::*;
 
 impl WidgetSize for durf_parser::Section {
-    fn height(&self, area: ratatui::prelude::Rect, style: &DurfWidgetStyle) -> usize {
+    fn height(
+        &self,
+        area: ratatui::prelude::Rect,
+        style: &DurfWidgetStyle,
+        ctx: &mut SizeContext,
+    ) -> usize {
+        if self.kind.is_chrome() {
+            if let ChromeStyle::Hide = style.general.section.chrome {
+                ctx.disclosure += count_disclosures(&self.nodes);
+                return 0;
+            }
+        }
+
         let mut total = match style.general.section.border {
             BorderStyle::None => 0,
             BorderStyle::Spacing => 1,
@@ -19,13 +31,99 @@ impl WidgetSize for durf_parser::Section {
             },
             height: 0,
         };
-        for node in &self.nodes {
-            total += match &**node {
-                durf_parser::RawNode::Empty => 0,
-                durf_parser::RawNode::Section(s) => s.height(area, style),
-                durf_parser::RawNode::Text(t) => t.height(area, style),
-            };
+        if let Some(title) = &self.title {
+            total += title.height(area, style, ctx);
+        }
+        match self.ordering {
+            durf_parser::SectionOrdering::Definition => {
+                total += definitions_height(self, area, style, ctx);
+            }
+            _ => {
+                for node in &self.nodes {
+                    total += node.height(area, style, ctx);
+                }
+            }
         }
         total
     }
 }
+
+/// Width of the indent for definition descriptions.
+const DESCRIPTION_INDENT: u16 = 2;
+
+/// Height of the groups of a definition list.
+fn definitions_height(
+    section: &durf_parser::Section,
+    area: Rect,
+    style: &DurfWidgetStyle,
+    ctx: &mut SizeContext,
+) -> usize {
+    let description_area = Rect {
+        width: area.width.saturating_sub(DESCRIPTION_INDENT),
+        ..area
+    };
+    let mut total = 0;
+    for node in &section.nodes {
+        let durf_parser::RawNode::Section(group) = &**node else {
+            total += node.height(area, style, ctx);
+            continue;
+        };
+        if let Some(term) = group.title.as_ref().filter(|t| !t.is_blank()) {
+            total += term.height(area, style, ctx);
+        }
+        for description in &group.nodes {
+            total += description.height(description_area, style, ctx);
+        }
+    }
+    total
+}
+
+/// Render a definition list, with bold terms and indented descriptions.
+pub(crate) fn render_definitions(
+    section: &durf_parser::Section,
+    mut area: Rect,
+    buf: &mut Buffer,
+    state: &mut DurfWidgetState,
+    style: &DurfWidgetStyle,
+    ctx: &mut DurfNodeWidgetContext,
+) {
+    for node in &section.nodes {
+        let mut size_ctx = SizeContext::new(state, ctx.disclosure);
+        let durf_parser::RawNode::Section(group) = &**node else {
+            let height = node.height(area, style, &mut size_ctx) as u16;
+            render_nodes(std::slice::from_ref(node), area, buf, state, style, ctx);
+            area.height = area.height.saturating_sub(height);
+            area.y = area.y.saturating_add(height);
+            continue;
+        };
+
+        // Render term.
+        if let Some(term) = group.title.as_ref().filter(|t| !t.is_blank()) {
+            let mut term = term.clone();
+            for frag in &mut term.fragments {
+                frag.attributes.bold = true;
+            }
+            let height = term.height(area, style, &mut size_ctx) as u16;
+            render_text(&term, area, buf, state, style, ctx);
+            area.height = area.height.saturating_sub(height);
+            area.y = area.y.saturating_add(height);
+        }
+
+        // Render descriptions.
+        let description_area = Rect {
+            x: area.x.saturating_add(DESCRIPTION_INDENT),
+            width: area.width.saturating_sub(DESCRIPTION_INDENT),
+            ..area
+        };
+        let mut size_ctx = SizeContext::new(state, ctx.disclosure);
+        let height: usize = group
+            .nodes
+            .iter()
+            .map(|n| n.height(description_area, style, &mut size_ctx))
+            .sum();
+        render_nodes(&group.nodes, description_area, buf, state, style, ctx);
+        area.height = area.height.saturating_sub(height as u16);
+        area.y = area.y.saturating_add(height as u16);
+        ctx.index += 1;
+    }
+}
@@ -1,7 +1,12 @@
 use super::*;
 
 impl WidgetSize for durf_parser::Text {
-    fn height(&self, area: ratatui::prelude::Rect, _style: &DurfWidgetStyle) -> usize {
+    fn height(
+        &self,
+        area: ratatui::prelude::Rect,
+        _style: &DurfWidgetStyle,
+        _ctx: &mut SizeContext,
+    ) -> usize {
         if area.width == 0 {
             return 0;
         }
@@ -15,13 +20,57 @@ impl WidgetSize for durf_parser::Text {
                 }
             }
         });
-        let total_lines = total_chars.div_ceil(area.width as usize);
-        total_lines
+        total_chars.div_ceil(area.width as usize)
     }
 }
 
+/// Render text into the area, registering focusable fragments.
+pub(crate) fn render_text(
+    text: &durf_parser::Text,
+    area: Rect,
+    buf: &mut Buffer,
+    state: &mut DurfWidgetState,
+    style: &DurfWidgetStyle,
+    ctx: &mut DurfNodeWidgetContext,
+) {
+    let mut total_text_len = 0usize;
+    let text_dir = text.direction();
+    let line = ratatui::text::Line::from_iter(text.fragments.iter().map(|f| {
+        let mut span = frag_to_span(f, style);
+        if let Some(index) = state.focused_element {
+            if index == ctx.index {
+                span = span.bg(Color::Black).fg(Color::White);
+            }
+        }
+        if f.attributes.link.is_some() {
+            span = span.underlined();
+            let mut focus = FocusableNode::new(ctx.index);
+            {
+                // Add rects for focus.
+                // TODO: Calculate more intelligently than starting line
+                // number.
+                let line: u16 = (total_text_len as u16).div_euclid(area.width) + area.y;
+                focus.rect.push(Rect::new(area.x, line, area.width, 1));
+            }
+            state.focusable.push(focus);
+        }
+        ctx.index += 1;
+        total_text_len += span.content.len();
+        span
+    }));
+    let text = ratatui::text::Text::from(vec![line]);
+    let mut p = Paragraph::new(text).wrap(Wrap { trim: false });
+    if text_dir == Some(durf_parser::Direction::Rtl) {
+        p = p.alignment(ratatui::layout::Alignment::Right);
+    }
+    p.render(area, buf);
+}
+
 /// Convert a fragment into a span.
-pub(crate) fn frag_to_span(value: &durf_parser::TextFragment) -> ratatui::text::Span<'static> {
+pub(crate) fn frag_to_span(
+    value: &durf_parser::TextFragment,
+    style: &DurfWidgetStyle,
+) -> ratatui::text::Span<'static> {
     let mut span = ratatui::text::Span::raw(value.text.to_string());
 
     if let Some(heading) = &value.attributes.heading {
@@ -47,7 +96,19 @@ pub(crate) fn frag_to_span(value: &durf_parser::TextFragment) -> ratatui::text::
         span = span.bg(Color::White);
         span = span.fg(Color::Black);
     }
-    if let Some(_) = &value.attributes.link {
+    if let Some(highlight) = &value.attributes.highlight {
+        span = span.fg(style.general.text.highlight_color(highlight));
+    }
+    if let Some(fg) = value.attributes.fg {
+        span = span.fg(Color::Rgb(fg.r, fg.g, fg.b));
+    }
+    if let Some(bg) = value.attributes.bg {
+        span = span.bg(Color::Rgb(bg.r, bg.g, bg.b));
+    }
+    if value.attributes.weight.is_some_and(|w| w <= 300) {
+        span = span.dim();
+    }
+    if value.attributes.link.is_some() {
         span = span.underlined();
     }
 
@@ -13,5 +13,7 @@ pub(crate) mod internal {
         widgets::{Block, Padding, Paragraph, StatefulWidget, Wrap},
     };
 
-    pub(crate) use crate::nodes::{DurfNodeWidget, WidgetSize};
+    pub(crate) use std::collections::HashSet;
+
+    pub(crate) use crate::nodes::{DurfNodeWidget, SizeContext, WidgetSize};
 }
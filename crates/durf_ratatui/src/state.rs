@@ -21,6 +21,8 @@ pub struct DurfWidgetState {
     pub(crate) focused_element: Option<usize>,
     /// Whether or not the widget should fully rerender.
     pub(crate) should_rerender: bool,
+    /// Disclosures toggled from their initial state, by document order.
+    pub(crate) toggled_disclosures: HashSet<usize>,
 }
 
 impl Default for DurfWidgetState {
@@ -33,6 +35,7 @@ impl Default for DurfWidgetState {
             focusable: Vec::new(),
             focused_element: None,
             should_rerender: true,
+            toggled_disclosures: HashSet::new(),
         }
     }
 }
@@ -45,11 +48,23 @@ impl DurfWidgetState {
                 .saturating_add(lines as usize)
                 .min(self.scrollbar_height());
         } else {
-            self.scroll = self.scroll.saturating_sub(lines.abs() as usize);
+            self.scroll = self.scroll.saturating_sub(lines.unsigned_abs() as usize);
             self.should_rerender = true;
         }
     }
 
+    /// Toggle a disclosure, counted in document order.
+    pub fn toggle_disclosure(&mut self, ordinal: usize) {
+        if !self.toggled_disclosures.remove(&ordinal) {
+            self.toggled_disclosures.insert(ordinal);
+        }
+        self.should_rerender = true;
+    }
+
+    pub(crate) fn is_disclosure_open(&self, ordinal: usize, initially_open: bool) -> bool {
+        initially_open != self.toggled_disclosures.contains(&ordinal)
+    }
+
     pub(crate) fn scrollbar_height(&self) -> usize {
         (self.buf.area().height as usize).saturating_sub(self.rendered_area.height as usize)
     }
@@ -63,6 +78,8 @@ pub(crate) struct FocusableNode {
     pub(crate) index: usize,
     /// Rect of node, relative to the full buf (not rendered).
     pub(crate) rect: Vec<Rect>,
+    /// Disclosure toggled by the node, if any.
+    pub(crate) disclosure: Option<usize>,
 }
 
 impl FocusableNode {
@@ -70,6 +87,7 @@ impl FocusableNode {
         Self {
             index,
             rect: Vec::new(),
+            disclosure: None,
         }
     }
 }
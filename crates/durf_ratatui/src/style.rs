@@ -9,12 +9,15 @@ pub struct DurfWidgetStyle {
 pub struct GeneralStyle {
     pub section: SectionStyle,
     pub text: TextStyle,
+    pub code: CodeStyle,
 }
 
 /// Section style.
 #[derive(Debug, Clone, Default)]
 pub struct SectionStyle {
     pub border: BorderStyle,
+    /// How navigation, header, and footer sections are shown.
+    pub chrome: ChromeStyle,
 }
 
 /// Section border style.
@@ -31,6 +34,100 @@ pub enum BorderStyle {
     FullBorder,
 }
 
-/// Text style.
+/// Style for page chrome sections.
 #[derive(Debug, Clone, Default)]
-pub struct TextStyle {}
+pub enum ChromeStyle {
+    /// Show chrome like any other section.
+    #[default]
+    Show,
+    /// De-emphasize chrome.
+    Dim,
+    /// Do not show chrome.
+    Hide,
+}
+
+/// Text style.
+#[derive(Debug, Clone)]
+pub struct TextStyle {
+    /// Colors of named highlight categories from semantic rules.
+    pub highlights: std::collections::HashMap<String, ratatui::style::Color>,
+    /// Color of highlight categories without their own color.
+    pub highlight: ratatui::style::Color,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            highlights: std::collections::HashMap::new(),
+            highlight: ratatui::style::Color::Yellow,
+        }
+    }
+}
+
+impl TextStyle {
+    /// Color of a highlight category.
+    pub fn highlight_color(&self, name: &str) -> ratatui::style::Color {
+        self.highlights.get(name).copied().unwrap_or(self.highlight)
+    }
+}
+
+/// Code block style, with colors for highlighted tokens.
+#[derive(Debug, Clone)]
+pub struct CodeStyle {
+    pub keyword: ratatui::style::Color,
+    pub types: ratatui::style::Color,
+    pub constant: ratatui::style::Color,
+    pub string: ratatui::style::Color,
+    pub number: ratatui::style::Color,
+    pub comment: ratatui::style::Color,
+    pub function: ratatui::style::Color,
+    pub variable: ratatui::style::Color,
+    pub property: ratatui::style::Color,
+    pub tag: ratatui::style::Color,
+    pub attribute: ratatui::style::Color,
+    pub operator: ratatui::style::Color,
+    pub punctuation: ratatui::style::Color,
+}
+
+impl CodeStyle {
+    /// Get the color of a token class.
+    pub fn token_color(&self, token: durf_parser::TokenClass) -> ratatui::style::Color {
+        use durf_parser::TokenClass;
+        match token {
+            TokenClass::Keyword => self.keyword,
+            TokenClass::Type => self.types,
+            TokenClass::Constant => self.constant,
+            TokenClass::String => self.string,
+            TokenClass::Number => self.number,
+            TokenClass::Comment => self.comment,
+            TokenClass::Function => self.function,
+            TokenClass::Variable => self.variable,
+            TokenClass::Property => self.property,
+            TokenClass::Tag => self.tag,
+            TokenClass::Attribute => self.attribute,
+            TokenClass::Operator => self.operator,
+            TokenClass::Punctuation => self.punctuation,
+        }
+    }
+}
+
+impl Default for CodeStyle {
+    fn default() -> Self {
+        use ratatui::style::Color;
+        Self {
+            keyword: Color::Magenta,
+            types: Color::Yellow,
+            constant: Color::Cyan,
+            string: Color::Green,
+            number: Color::LightRed,
+            comment: Color::DarkGray,
+            function: Color::Blue,
+            variable: Color::LightCyan,
+            property: Color::LightBlue,
+            tag: Color::Red,
+            attribute: Color::Yellow,
+            operator: Color::Reset,
+            punctuation: Color::Gray,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Tests.
+
+use super::*;
+
+use durf_parser::{Ast, ParseFlags};
+
+/// Render a document into a buffer.
+fn render(document: &str, area: Rect) -> Buffer {
+    let ast = Ast::from_html(document, ParseFlags::default()).unwrap();
+    let mut state = DurfWidgetState::default();
+    let style = DurfWidgetStyle::default();
+    let mut buf = Buffer::empty(area);
+    DurfWidget::new(&ast, &mut state, &style).render(area, &mut buf);
+    buf
+}
+
+#[test_log::test]
+fn malicious_text_is_not_rendered() {
+    let document = "
+        <p>Title\u{1b}]0;pwned\u{7}</p>
+        <p>Clear\u{1b}[2J\u{9b}1;1H screen</p>
+        <p>evil\u{202e}txt.exe</p>
+        <form><input name=\"q\" value=\"\u{1b}[31m\"></form>
+    ";
+    let buf = render(document, Rect::new(0, 0, 60, 20));
+    let rendered: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+    tracing::trace!("{rendered}");
+
+    assert!(
+        !rendered
+            .chars()
+            .any(|c| c.is_control() || durf_parser::is_bidi_control(c))
+    );
+    assert!(rendered.contains("eviltxt.exe"));
+    assert!(rendered.contains("␛"));
+}
+
+#[test_log::test]
+fn rtl_text_is_right_aligned() {
+    let buf = render(r#"<p dir="rtl">abc</p><p>def</p>"#, Rect::new(0, 0, 20, 4));
+    let lines: Vec<String> = (0..buf.area.height)
+        .map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect())
+        .collect();
+    tracing::trace!("{lines:#?}");
+
+    assert!(lines.iter().any(|l| l.contains("  abc")));
+    assert!(lines.iter().any(|l| l.contains("def  ")));
+}
@@ -13,11 +13,10 @@ pub struct MouseCapture;
 #[cfg(feature = "crossterm")]
 impl MouseCapture {
     pub fn new() -> Result<Self, Error> {
-        if let Err(_) = crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture)
-        {
+        if crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture).is_err() {
             return Err(Error::Crossterm);
         }
-        return Ok(Self);
+        Ok(Self)
     }
 }
 
@@ -23,6 +23,10 @@ impl<'a> DurfWidget<'a> {
         for focusable in &self.state.focusable {
             for rect in &focusable.rect {
                 if rect.contains(pos) {
+                    if let Some(ordinal) = focusable.disclosure {
+                        self.state.toggle_disclosure(ordinal);
+                        return None;
+                    }
                     self.state.focused_element = Some(focusable.index);
                     self.state.should_rerender = true;
                     // return Some(DurfEvent::FollowLink()))
@@ -47,11 +51,14 @@ impl<'a> Widget for DurfWidget<'a> {
         self.state.should_rerender |= area.width != self.state.rendered_area.width;
         if self.state.should_rerender {
             // Render the full widget/buffer.
-            let total_height = self.ast.root.height(area, self.style);
+            let total_height =
+                self.ast
+                    .root
+                    .height(area, self.style, &mut SizeContext::new(self.state, 0));
             let full_content_area = Rect::new(0, 0, area.width, total_height as u16);
-            let mut full_buf = Buffer::empty(full_content_area.clone());
+            let mut full_buf = Buffer::empty(full_content_area);
 
-            let mut ctx = DurfNodeWidgetContext::new(&self.state);
+            let mut ctx = DurfNodeWidgetContext::new(self.state);
             self.state.focusable.clear();
 
             let widget = DurfNodeWidget {
@@ -61,7 +68,7 @@ impl<'a> Widget for DurfWidget<'a> {
                 ctx: &mut ctx,
             };
             widget.render(full_content_area, &mut full_buf);
-            self.state.rendered_area = area.clone();
+            self.state.rendered_area = area;
             self.state.buf = full_buf;
             self.state.should_rerender = false;
         }
@@ -82,7 +89,7 @@ impl<'a> Widget for DurfWidget<'a> {
         }
 
         // Render floating scrollbar.
-        let scrollbar_needed = self.state.buf.area().height as u16 > area.height;
+        let scrollbar_needed = self.state.buf.area().height > area.height;
         if scrollbar_needed {
             let mut scrollbar_state =
                 ratatui::widgets::ScrollbarState::new(self.state.scrollbar_height())
@@ -114,6 +121,8 @@ pub(crate) struct DurfNodeWidgetContext {
     pub(crate) height: usize,
     /// Widget index.
     pub(crate) index: usize,
+    /// Ordinal of the next disclosure.
+    pub(crate) disclosure: usize,
 }
 
 impl DurfNodeWidgetContext {
@@ -122,6 +131,7 @@ impl DurfNodeWidgetContext {
             offset: state.scroll,
             height: 0,
             index: 0,
+            disclosure: 0,
         }
     }
 }